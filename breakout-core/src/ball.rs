@@ -19,6 +19,10 @@ impl Ball {
         }
     }
 
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius
+    }
+
     pub fn spin(&self) -> f32 {
         self.spin
     }
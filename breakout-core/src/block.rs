@@ -1,20 +1,103 @@
 use prelude::*;
 use shape::Box;
 
-#[derive(Debug, Copy, Clone)]
+/// Points for destroying a block with a single hit point, before any multiplier.
+const BLOCK_POINTS: u32 = 10;
+
+/// How a block reacts to being hit.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BlockKind {
+    /// Loses a hit point every time the ball hits it.
+    Normal,
+
+    /// Can never be destroyed. The level is cleared without having to break these.
+    Steel,
+
+    /// Only loses hit points when hit by a ball moving at least `min_speed`.
+    Armored { min_speed: f32 },
+
+    /// Blows up when destroyed, taking a hit point off of every block within `radius` of its
+    /// center. Explosions can set off other explosive blocks.
+    Explosive { radius: f32 },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Block {
     dimensions: vec2,
     origin: vec2,
+    kind: BlockKind,
+    hit_points: u32,
+    max_hit_points: u32,
 }
 
 impl Block {
+    /// Creates a normal block that breaks the first time it is hit.
     pub fn new(dimensions: vec2, origin: vec2) -> Self {
-        Self { dimensions, origin }
+        Self {
+            dimensions,
+            origin,
+            kind: BlockKind::Normal,
+            hit_points: 1,
+            max_hit_points: 1,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: BlockKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets how many times the block has to be hit before it breaks.
+    pub fn with_hit_points(mut self, hit_points: u32) -> Self {
+        self.hit_points = hit_points;
+        self.max_hit_points = hit_points;
+        self
     }
 
     pub fn dimensions(&self) -> vec2 {
         self.dimensions
     }
+
+    pub fn kind(&self) -> BlockKind {
+        self.kind
+    }
+
+    pub fn hit_points(&self) -> u32 {
+        self.hit_points
+    }
+
+    pub fn max_hit_points(&self) -> u32 {
+        self.max_hit_points
+    }
+
+    pub fn is_destructible(&self) -> bool {
+        self.kind != BlockKind::Steel
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.is_destructible() && self.hit_points == 0
+    }
+
+    /// What the block is worth when destroyed. Tougher blocks are worth more.
+    pub fn points(&self) -> u32 {
+        let points = BLOCK_POINTS.saturating_mul(self.max_hit_points);
+        match self.kind {
+            BlockKind::Armored { .. } => points.saturating_mul(2),
+            BlockKind::Normal | BlockKind::Steel | BlockKind::Explosive { .. } => points,
+        }
+    }
+
+    /// Hits the block with a ball moving at `speed`. Returns whether the block took any damage.
+    pub(crate) fn hit(&mut self, speed: f32) -> bool {
+        match self.kind {
+            BlockKind::Steel => false,
+            BlockKind::Armored { min_speed } if speed < min_speed => false,
+            BlockKind::Normal | BlockKind::Armored { .. } | BlockKind::Explosive { .. } => {
+                self.hit_points = self.hit_points.saturating_sub(1);
+                true
+            }
+        }
+    }
 }
 
 impl Shape for Block {
@@ -0,0 +1,216 @@
+use prelude::*;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How far a player has got through a campaign: the level they are on, and the points and lives
+/// they started it with. It is saved as `key: value` lines, like the header of a level.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub level: usize,
+    pub points: u32,
+    pub lives: u32,
+
+    /// The `Campaign::checksum` of the levels the progress is for, so that progress isn't
+    /// picked back up with a different list of levels.
+    pub checksum: u64,
+}
+
+impl Progress {
+    pub fn parse(text: &str) -> Result<Self, LevelError> {
+        let mut level = None;
+        let mut points = None;
+        let mut lives = None;
+        let mut checksum = None;
+
+        for (number, line) in text.lines().enumerate().map(|(i, line)| (i + 1, line)) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let (key, value) = match line.find(':') {
+                Some(colon) => (line[..colon].trim(), line[colon + 1..].trim()),
+                None => return Err(LevelError::new(number, "expected `key: value`")),
+            };
+
+            let bad_value =
+                || LevelError::new(number, format!("expected a number, not `{}`", value));
+            match key {
+                "level" => level = Some(value.parse().map_err(|_| bad_value())?),
+                "points" => points = Some(value.parse().map_err(|_| bad_value())?),
+                "lives" => lives = Some(value.parse().map_err(|_| bad_value())?),
+                "checksum" => checksum = Some(value.parse().map_err(|_| bad_value())?),
+                _ => return Err(LevelError::new(number, format!("unknown key `{}`", key))),
+            }
+        }
+
+        let line = text.lines().count();
+        Ok(Self {
+            level: level.ok_or_else(|| LevelError::new(line, "missing `level`"))?,
+            points: points.ok_or_else(|| LevelError::new(line, "missing `points`"))?,
+            lives: lives.ok_or_else(|| LevelError::new(line, "missing `lives`"))?,
+            checksum: checksum.ok_or_else(|| LevelError::new(line, "missing `checksum`"))?,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "level: {}", self.level)?;
+        writeln!(f, "points: {}", self.points)?;
+        writeln!(f, "lives: {}", self.lives)?;
+        writeln!(f, "checksum: {}", self.checksum)
+    }
+}
+
+/// An ordered list of levels played one after another. Points and lives carry over from each
+/// level to the next, and the next level starts as soon as the current one is cleared.
+#[derive(Debug, Clone)]
+pub struct Campaign {
+    dt: f32,
+    levels: Vec<Level>,
+    progress: Progress,
+    game: Breakout,
+}
+
+impl Campaign {
+    /// Starts a campaign from its first level, with that level's lives.
+    pub fn new(dt: f32, levels: Vec<Level>) -> Result<Self, CampaignError> {
+        let lives = match levels.first() {
+            Some(level) => level.builder().lives,
+            None => return Err(CampaignError::NoLevels),
+        };
+
+        let progress = Progress {
+            level: 0,
+            points: 0,
+            lives,
+            checksum: Self::checksum(&levels),
+        };
+        Self::resume(dt, levels, progress)
+    }
+
+    /// Picks a campaign back up from saved progress. Every level is checked up front so that a
+    /// broken one doesn't turn up halfway through, and so is the progress, since it may have
+    /// been saved for other levels or edited by hand.
+    pub fn resume(dt: f32, levels: Vec<Level>, progress: Progress) -> Result<Self, CampaignError> {
+        if levels.is_empty() {
+            return Err(CampaignError::NoLevels);
+        }
+
+        for (index, level) in levels.iter().enumerate() {
+            level
+                .builder()
+                .dt(dt)
+                .try_build()
+                .map_err(|error| CampaignError::InvalidLevel { index, error })?;
+        }
+
+        if progress.checksum != Self::checksum(&levels) {
+            return Err(CampaignError::WrongLevels);
+        }
+        if progress.level >= levels.len() {
+            return Err(CampaignError::NoSuchLevel {
+                level: progress.level,
+                levels: levels.len(),
+            });
+        }
+
+        let game = levels[progress.level]
+            .builder()
+            .dt(dt)
+            .points(progress.points)
+            .lives(progress.lives)
+            .try_build()
+            .map_err(CampaignError::InvalidProgress)?;
+        Ok(Self {
+            dt,
+            levels,
+            progress,
+            game,
+        })
+    }
+
+    fn build(dt: f32, level: &Level, progress: Progress) -> Breakout {
+        level
+            .builder()
+            .dt(dt)
+            .points(progress.points)
+            .lives(progress.lives)
+            .build()
+    }
+
+    /// Identifies a list of levels by what is in them.
+    pub fn checksum(levels: &[Level]) -> u64 {
+        // FNV-1a, which unlike the standard library's hashers is the same on every build.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for level in levels {
+            for byte in level.to_string().bytes().chain(Some(0)) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        hash
+    }
+
+    pub fn levels(&self) -> &[Level] {
+        &self.levels[..]
+    }
+
+    /// The index of the level being played.
+    pub fn level(&self) -> usize {
+        self.progress.level
+    }
+
+    /// What to save to pick the campaign back up at the start of the current level.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    pub fn game(&self) -> &Breakout {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Breakout {
+        &mut self.game
+    }
+
+    /// True once the last level has been cleared.
+    pub fn is_finished(&self) -> bool {
+        self.game.phase() == Phase::LevelCleared && self.progress.level + 1 == self.levels.len()
+    }
+
+    /// Plays the current level again, with the points and lives the player started it with.
+    pub fn restart_level(&mut self) {
+        self.game = Self::build(self.dt, &self.levels[self.progress.level], self.progress);
+    }
+
+    /// Ticks the current level. When it is cleared the next level takes its place right away,
+    /// so a `LevelCleared` event means the game's blocks have all been replaced.
+    pub fn tick(&mut self) -> Vec<GameEvent> {
+        let events = self.game.tick();
+
+        if self.game.phase() == Phase::LevelCleared && !self.is_finished() {
+            self.progress = Progress {
+                level: self.progress.level + 1,
+                points: self.game.score().points(),
+                lives: self.game.lives(),
+                checksum: self.progress.checksum,
+            };
+            self.restart_level();
+        }
+
+        events
+    }
+}
@@ -0,0 +1,53 @@
+//! The colors everything is drawn in, shared by the window and by `Frame`.
+
+use prelude::*;
+
+pub const CLEAR_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+pub const WHITE: [f32; 3] = [1., 1., 1.];
+pub const RED: [f32; 3] = [1., 0., 0.];
+pub const BALL_COLOR: [f32; 3] = RED;
+pub const PADDLE_COLOR: [f32; 3] = WHITE;
+pub const BLOCK_COLOR: [f32; 3] = [0.37, 0.64, 0.82];
+pub const ARMORED_BLOCK_COLOR: [f32; 3] = [0.85, 0.55, 0.2];
+pub const EXPLOSIVE_BLOCK_COLOR: [f32; 3] = [0.9, 0.25, 0.2];
+pub const STEEL_BLOCK_COLOR: [f32; 3] = [0.6, 0.6, 0.62];
+pub const WIDE_PADDLE_COLOR: [f32; 3] = [0.3, 0.8, 0.3];
+pub const SLOW_BALL_COLOR: [f32; 3] = [0.3, 0.5, 0.95];
+pub const EXTRA_LIFE_COLOR: [f32; 3] = [0.95, 0.4, 0.7];
+pub const BIG_BALL_COLOR: [f32; 3] = [0.95, 0.85, 0.25];
+pub const SMALL_BALL_COLOR: [f32; 3] = [0.6, 0.35, 0.85];
+pub const MULTI_BALL_COLOR: [f32; 3] = [0.95, 0.55, 0.25];
+pub const LASER_COLOR: [f32; 3] = [0.9, 0.2, 0.2];
+pub const CATCH_COLOR: [f32; 3] = [0.3, 0.85, 0.85];
+pub const PROJECTILE_COLOR: [f32; 3] = [1., 0.9, 0.4];
+
+pub fn block_color(block: &Block) -> [f32; 3] {
+    let base = match block.kind() {
+        BlockKind::Normal => BLOCK_COLOR,
+        BlockKind::Armored { .. } => ARMORED_BLOCK_COLOR,
+        BlockKind::Explosive { .. } => EXPLOSIVE_BLOCK_COLOR,
+        BlockKind::Steel => return STEEL_BLOCK_COLOR,
+    };
+
+    // Blocks fade as they lose hit points.
+    let health = block.hit_points() as f32 / block.max_hit_points() as f32;
+    let brightness = 0.4 + 0.6 * health;
+    [
+        base[0] * brightness,
+        base[1] * brightness,
+        base[2] * brightness,
+    ]
+}
+
+pub fn capsule_color(power_up: PowerUp) -> [f32; 3] {
+    match power_up {
+        PowerUp::WidePaddle => WIDE_PADDLE_COLOR,
+        PowerUp::SlowBall => SLOW_BALL_COLOR,
+        PowerUp::ExtraLife => EXTRA_LIFE_COLOR,
+        PowerUp::BigBall => BIG_BALL_COLOR,
+        PowerUp::SmallBall => SMALL_BALL_COLOR,
+        PowerUp::MultiBall => MULTI_BALL_COLOR,
+        PowerUp::Laser => LASER_COLOR,
+        PowerUp::Catch => CATCH_COLOR,
+    }
+}
@@ -0,0 +1,23 @@
+/// Everything the player can do on one tick. Commands are applied with `Breakout::apply` just
+/// before the tick they are for, which is what lets a replay play a game back exactly.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Command {
+    /// Which way to steer the paddle, from -1 for full speed left to 1 for full speed right.
+    pub steer: f32,
+
+    pub launch: bool,
+    pub fire: bool,
+
+    /// Pauses the game, or resumes it if it is already paused.
+    pub pause: bool,
+}
+
+impl Command {
+    /// Just steering, with no buttons pressed.
+    pub fn steer(steer: f32) -> Self {
+        Self {
+            steer,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use prelude::*;
+
+/// Plays the game in place of a person. It is asked what to do before every tick.
+pub trait PaddleController {
+    fn command(&mut self, game: &Breakout) -> Command;
+}
+
+/// Keeps the middle of the paddle under the lowest ball. Easy to beat, since it only reacts to
+/// where the ball is now.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TrackingController;
+
+impl TrackingController {
+    pub fn new() -> Self {
+        TrackingController
+    }
+}
+
+impl PaddleController for TrackingController {
+    fn command(&mut self, game: &Breakout) -> Command {
+        let lowest = game
+            .balls()
+            .iter()
+            .min_by(|a, b| a.location().y().total_cmp(&b.location().y()));
+
+        Command {
+            steer: lowest.map_or(0., |ball| steer_towards(game, ball.location().x())),
+            ..buttons(game)
+        }
+    }
+}
+
+/// Works out where each ball will come down, bouncing it off of the walls along the way, and
+/// gets there first. It hits the ball with the end of the paddle that sends it towards the
+/// blocks that are left.
+///
+/// Blocks in the ball's way aren't taken into account, nor is spin, so it can still be caught
+/// out.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PredictiveController;
+
+impl PredictiveController {
+    pub fn new() -> Self {
+        PredictiveController
+    }
+
+    /// Where `ball` will cross the line `y`, and how long it will take to get there.
+    pub fn predict(game: &Breakout, ball: &Ball, y: f32) -> Option<(f32, f32)> {
+        let radius = ball.radius();
+        let [x, ball_y] = ball.location();
+        let [vx, vy] = ball.velocity();
+        let top = 1. - radius;
+
+        let time = if vy < 0. {
+            (ball_y - y) / -vy
+        } else if vy > 0. {
+            // Up to the top of the arena and back down, slowed by the bounce.
+            let down = vy * game.physics().wall_restitution;
+            if down <= 0. {
+                return None;
+            }
+            (top - ball_y) / vy + (top - y) / down
+        } else {
+            return None;
+        };
+
+        if time < 0. {
+            return None;
+        }
+
+        // Bouncing between the side walls is the same as carrying on through them into mirror
+        // images of the arena.
+        let width = 1. - 2. * radius;
+        let unfolded = (x - radius + vx * time).rem_euclid(2. * width);
+        let x = if unfolded > width {
+            2. * width - unfolded
+        } else {
+            unfolded
+        };
+
+        Some((radius + x, time))
+    }
+
+    /// How far from the middle of the paddle to hit the ball, from -1 to 1, to send it towards
+    /// the blocks.
+    fn aim(game: &Breakout, landing: f32) -> f32 {
+        let blocks: Vec<&Block> = game
+            .blocks()
+            .iter()
+            .flatten()
+            .filter(|block| block.is_destructible())
+            .collect();
+        if blocks.is_empty() {
+            return 0.;
+        }
+
+        let target = blocks
+            .iter()
+            .map(|block| (block.left() + block.right()) / 2.)
+            .sum::<f32>()
+            / blocks.len() as f32;
+
+        ((target - landing) * 2.).clamp(-0.6, 0.6)
+    }
+}
+
+impl PaddleController for PredictiveController {
+    fn command(&mut self, game: &Breakout) -> Command {
+        let paddle = game.paddle();
+
+        // The ball that will get to the paddle first is the one to worry about.
+        let landing = game
+            .balls()
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| !game.ball_is_held(i))
+            .filter_map(|(_, ball)| Self::predict(game, ball, paddle.top() + ball.radius()))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let steer = match landing {
+            Some((x, _)) => {
+                let [width, _] = paddle.dimensions();
+                let offset = Self::aim(game, x) * width / 2.;
+                steer_towards(game, x - offset)
+            }
+            None => steer_towards(game, 0.5),
+        };
+
+        Command {
+            steer,
+            ..buttons(game)
+        }
+    }
+}
+
+/// The steering that moves the middle of the paddle to `x` as quickly as possible without
+/// going past it.
+fn steer_towards(game: &Breakout, x: f32) -> f32 {
+    let paddle = game.paddle();
+    let middle = (paddle.left() + paddle.right()) / 2.;
+    let step = game.physics().paddle_speed * game.dt();
+    ((x - middle) / step).clamp(-1., 1.)
+}
+
+/// Launches served or caught balls and fires the laser whenever it can.
+pub(crate) fn buttons(game: &Breakout) -> Command {
+    let held = (0..game.balls().len()).any(|i| game.ball_is_held(i));
+    Command {
+        launch: game.phase() == Phase::Serve || held,
+        fire: game.laser_active(),
+        ..Command::default()
+    }
+}
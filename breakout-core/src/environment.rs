@@ -0,0 +1,198 @@
+use controller;
+use prelude::*;
+
+/// What an agent can do on each step.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Action {
+    /// One of `Environment::DISCRETE_ACTIONS` actions: 0 does nothing, 1 steers left at full
+    /// speed, 2 steers right at full speed, 3 launches the ball and 4 fires the laser.
+    Discrete(usize),
+
+    /// Steering from -1 to 1. The ball is launched and the laser is fired whenever they can be,
+    /// so steering is the only thing left to learn.
+    Continuous(f32),
+}
+
+impl Action {
+    fn command(self, game: &Breakout) -> Result<Command, ActionError> {
+        Ok(match self {
+            Action::Discrete(0) => Command::default(),
+            Action::Discrete(1) => Command::steer(-1.),
+            Action::Discrete(2) => Command::steer(1.),
+            Action::Discrete(3) => Command {
+                launch: true,
+                ..Command::default()
+            },
+            Action::Discrete(4) => Command {
+                fire: true,
+                ..Command::default()
+            },
+            Action::Discrete(action) => return Err(ActionError::NoSuchAction(action)),
+            Action::Continuous(steer) if steer.is_nan() => {
+                return Err(ActionError::InvalidSteering(steer))
+            }
+            Action::Continuous(steer) => Command {
+                steer: steer.clamp(-1., 1.),
+                ..controller::buttons(game)
+            },
+        })
+    }
+}
+
+/// What happened on a step besides the reward.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepInfo {
+    /// Everything that happened on each of the step's ticks.
+    pub events: Vec<GameEvent>,
+    pub ticks: u32,
+    pub lives: u32,
+    pub points: u32,
+
+    /// True when the episode ended because it ran out of steps rather than because the game
+    /// was over.
+    pub truncated: bool,
+}
+
+/// What `Environment::step` returns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub observation: Vec<f32>,
+
+    /// The points scored during the step.
+    pub reward: f32,
+
+    /// True once the game is over, the level is cleared or the episode ran out of steps. Call
+    /// `reset` to start another episode.
+    pub done: bool,
+    pub info: StepInfo,
+}
+
+/// Wraps a level for reinforcement learning, in the style of a gym environment: `reset` starts
+/// an episode and `step` takes an action and says what came of it. Nothing is random but the
+/// seed given to `reset`, so the same seed and actions always play out the same.
+#[derive(Debug, Clone)]
+pub struct Environment {
+    level: BreakoutBuilder,
+    frame_skip: u32,
+    max_steps: Option<u32>,
+    game: Breakout,
+    steps: u32,
+}
+
+impl Environment {
+    /// How many actions `Action::Discrete` has.
+    pub const DISCRETE_ACTIONS: usize = 5;
+
+    /// An environment that plays `level`, which needs a `dt`.
+    pub fn new(level: BreakoutBuilder) -> Result<Self, BuildError> {
+        let game = level.clone().try_build()?;
+        Ok(Self {
+            level,
+            frame_skip: 1,
+            max_steps: None,
+            game,
+            steps: 0,
+        })
+    }
+
+    /// Repeats each action for `frame_skip` ticks. Buttons are only pressed on the first of
+    /// them. Defaults to 1.
+    pub fn frame_skip(mut self, frame_skip: u32) -> Self {
+        assert!(frame_skip > 0, "Each step needs at least one tick");
+        self.frame_skip = frame_skip;
+        self
+    }
+
+    /// Ends episodes after `max_steps` steps, even if the game isn't over. By default episodes
+    /// only end with the game.
+    pub fn max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Starts a new episode with the level seeded by `seed`, and returns the first observation.
+    pub fn reset(&mut self, seed: u64) -> Vec<f32> {
+        self.game = self.level.clone().seed(seed).build();
+        self.steps = 0;
+        self.observation()
+    }
+
+    /// Takes `action`, or says why it can't be taken. Agents are outside code, so a bad action
+    /// is an error rather than a panic, and the game is left as it was.
+    pub fn step(&mut self, action: Action) -> Result<Step, ActionError> {
+        let points = self.game.score().points();
+        let mut command = action.command(&self.game)?;
+        let mut events = vec![];
+        let mut ticks = 0;
+
+        while ticks < self.frame_skip && !self.is_over() {
+            self.game.apply(command);
+            events.extend(self.game.tick());
+            command = Command::steer(command.steer);
+            ticks += 1;
+        }
+        self.steps += 1;
+
+        let truncated = !self.is_over() && self.max_steps.is_some_and(|max| self.steps >= max);
+        Ok(Step {
+            observation: self.observation(),
+            reward: (self.game.score().points() - points) as f32,
+            done: self.is_over() || truncated,
+            info: StepInfo {
+                events,
+                ticks,
+                lives: self.game.lives(),
+                points: self.game.score().points(),
+                truncated,
+            },
+        })
+    }
+
+    fn is_over(&self) -> bool {
+        self.game.phase() == Phase::GameOver || self.game.phase() == Phase::LevelCleared
+    }
+
+    pub fn game(&self) -> &Breakout {
+        &self.game
+    }
+
+    /// How long every observation is for this level.
+    pub fn observation_len(&self) -> usize {
+        8 + self.game.blocks().len()
+    }
+
+    /// The game as a feature vector:
+    ///
+    /// - the middle of the paddle, its x velocity and its width,
+    /// - the first ball's location and velocity, and 1 if it is on the paddle waiting to be
+    ///   launched or 0 if not,
+    /// - and each block's hit points as a fraction of its most, which is 0 once it is destroyed.
+    ///   Every block starts with at least one hit point, so this is always a number.
+    ///
+    /// Blocks keep their place in the vector for the whole episode.
+    pub fn observation(&self) -> Vec<f32> {
+        let paddle = self.game.paddle();
+        let ball = self.game.ball();
+        let [width, _] = paddle.dimensions();
+        let [ball_x, ball_y] = ball.location();
+        let [ball_vx, ball_vy] = ball.velocity();
+        let waiting = self.game.phase() == Phase::Serve || self.game.ball_is_held(0);
+
+        let mut observation = Vec::with_capacity(self.observation_len());
+        observation.extend_from_slice(&[
+            (paddle.left() + paddle.right()) / 2.,
+            paddle.velocity().x(),
+            width,
+            ball_x,
+            ball_y,
+            ball_vx,
+            ball_vy,
+            if waiting { 1. } else { 0. },
+        ]);
+        observation.extend(self.game.blocks().iter().map(|block| match *block {
+            Some(ref block) => block.hit_points() as f32 / block.max_hit_points() as f32,
+            None => 0.,
+        }));
+        observation
+    }
+}
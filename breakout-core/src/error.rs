@@ -0,0 +1,236 @@
+use prelude::*;
+use std::error::Error;
+use std::fmt;
+
+/// Why `BreakoutBuilder::try_build` couldn't make a game.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    MissingDt,
+    MissingBall,
+    MissingPaddle,
+
+    /// The time step has to be a finite number of seconds greater than 0.
+    InvalidDt(f32),
+
+    /// The player has to start with at least one life.
+    NoLives,
+
+    /// The `PhysicsConfig` field called `name` is out of range. Speeds have to be finite and
+    /// greater than 0, `max_horizontal_fraction` has to be at least 0 and less than 1,
+    /// `min_vertical_speed` at least 0 and less than `max_ball_speed`, and `wall_restitution`
+    /// greater than 0 and at most 1.
+    InvalidPhysics {
+        name: &'static str,
+        value: f32,
+    },
+
+    /// The ball's radius has to be greater than 0.
+    InvalidBallRadius(f32),
+
+    /// The ball's velocity has to be finite.
+    InvalidBallVelocity(vec2),
+
+    /// A served ball is launched at the speed it was given, so it has to have one.
+    NoLaunchSpeed,
+
+    /// The paddle has to be wider and taller than 0.
+    InvalidPaddleDimensions(vec2),
+
+    /// The block at `index` has to be wider and taller than 0.
+    InvalidBlockDimensions {
+        index: usize,
+        dimensions: vec2,
+    },
+
+    /// The block at `index` has to start with at least one hit point.
+    NoHitPoints {
+        index: usize,
+    },
+
+    /// The block at `index` has a `min_speed` or `radius` that isn't a finite number of at
+    /// least 0.
+    InvalidBlockKind {
+        index: usize,
+        kind: BlockKind,
+    },
+
+    /// The chance of a drop has to be from 0 to 1.
+    InvalidDropChance(f32),
+
+    /// The par time has to be a finite number of seconds, at least 0.
+    InvalidParTime(f32),
+
+    /// The ball has to start entirely inside of the arena.
+    BallOutsideArena,
+
+    /// The paddle has to start entirely inside of the arena.
+    PaddleOutsideArena,
+
+    /// The block at `index` has to be entirely inside of the arena.
+    BlockOutsideArena {
+        index: usize,
+    },
+
+    /// The blocks at `first` and `second` overlap. Blocks may touch, but not overlap.
+    OverlappingBlocks {
+        first: usize,
+        second: usize,
+    },
+
+    /// The ball starts inside of the block at `index`.
+    BallInsideBlock {
+        index: usize,
+    },
+
+    /// The ball starts inside of the paddle.
+    BallInsidePaddle,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BuildError::MissingDt => write!(f, "User did not call BreakoutBuilder::dt(f32)"),
+            BuildError::MissingBall => write!(f, "User did not call BreakoutBuilder::ball(Ball)"),
+            BuildError::MissingPaddle => {
+                write!(f, "User did not call BreakoutBuilder::paddle(Paddle)")
+            }
+            BuildError::InvalidDt(dt) => {
+                write!(f, "dt must be a finite number greater than 0, not {}", dt)
+            }
+            BuildError::NoLives => write!(f, "The player must start with at least one life"),
+            BuildError::InvalidPhysics { name, value } => {
+                write!(f, "{} is out of range: {}", name, value)
+            }
+            BuildError::InvalidBallRadius(radius) => {
+                write!(
+                    f,
+                    "The ball's radius must be greater than 0, not {}",
+                    radius
+                )
+            }
+            BuildError::InvalidBallVelocity([vx, vy]) => {
+                write!(f, "The ball's velocity must be finite, not {} {}", vx, vy)
+            }
+            BuildError::NoLaunchSpeed => {
+                write!(f, "A served ball must have a speed to be launched at")
+            }
+            BuildError::InvalidPaddleDimensions([width, height]) => write!(
+                f,
+                "The paddle must be wider and taller than 0, not {} by {}",
+                width, height
+            ),
+            BuildError::InvalidBlockDimensions {
+                index,
+                dimensions: [width, height],
+            } => write!(
+                f,
+                "Block {} must be wider and taller than 0, not {} by {}",
+                index, width, height
+            ),
+            BuildError::NoHitPoints { index } => {
+                write!(f, "Block {} must have at least one hit point", index)
+            }
+            BuildError::InvalidBlockKind { index, kind } => {
+                write!(f, "Block {} has an invalid kind: {:?}", index, kind)
+            }
+            BuildError::InvalidDropChance(chance) => {
+                write!(
+                    f,
+                    "The chance of a drop must be from 0 to 1, not {}",
+                    chance
+                )
+            }
+            BuildError::InvalidParTime(par_time) => write!(
+                f,
+                "The par time must be a finite number of at least 0, not {}",
+                par_time
+            ),
+            BuildError::BallOutsideArena => write!(f, "The ball is outside of the arena"),
+            BuildError::PaddleOutsideArena => write!(f, "The paddle is outside of the arena"),
+            BuildError::BlockOutsideArena { index } => {
+                write!(f, "Block {} is outside of the arena", index)
+            }
+            BuildError::OverlappingBlocks { first, second } => {
+                write!(f, "Blocks {} and {} overlap", first, second)
+            }
+            BuildError::BallInsideBlock { index } => {
+                write!(f, "The ball starts inside of block {}", index)
+            }
+            BuildError::BallInsidePaddle => write!(f, "The ball starts inside of the paddle"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Why a `Campaign` couldn't be started.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CampaignError {
+    NoLevels,
+
+    /// The level at `index` can't be played.
+    InvalidLevel {
+        index: usize,
+        error: BuildError,
+    },
+
+    /// The progress was saved for a different list of levels.
+    WrongLevels,
+
+    /// The progress is for level `level`, but there are only `levels` levels.
+    NoSuchLevel {
+        level: usize,
+        levels: usize,
+    },
+
+    /// The level the progress is for can't be played with its points and lives.
+    InvalidProgress(BuildError),
+}
+
+impl fmt::Display for CampaignError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CampaignError::NoLevels => write!(f, "A campaign needs at least one level"),
+            CampaignError::InvalidLevel { index, ref error } => {
+                write!(f, "Level {}: {}", index + 1, error)
+            }
+            CampaignError::WrongLevels => {
+                write!(f, "The progress was saved for a different list of levels")
+            }
+            CampaignError::NoSuchLevel { level, levels } => write!(
+                f,
+                "The progress is for level {}, but there are only {} levels",
+                level + 1,
+                levels
+            ),
+            CampaignError::InvalidProgress(ref error) => write!(f, "Bad progress: {}", error),
+        }
+    }
+}
+
+impl Error for CampaignError {}
+
+/// Why `Environment::step` couldn't take an action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionError {
+    /// There are only `Environment::DISCRETE_ACTIONS` discrete actions.
+    NoSuchAction(usize),
+
+    /// Continuous steering has to be a number.
+    InvalidSteering(f32),
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ActionError::NoSuchAction(action) => {
+                write!(f, "There is no discrete action {}", action)
+            }
+            ActionError::InvalidSteering(steer) => {
+                write!(f, "Steering must be a number, not {}", steer)
+            }
+        }
+    }
+}
+
+impl Error for ActionError {}
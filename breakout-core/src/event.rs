@@ -0,0 +1,87 @@
+use prelude::*;
+
+/// The walls around the arena. The bottom of the arena is open.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Wall {
+    Left,
+    Right,
+    Top,
+}
+
+/// Something that happened in the game during a tick.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GameEvent {
+    /// The served ball was launched off of the paddle.
+    BallLaunched,
+
+    /// The ball touched the paddle. `offset` is where along the paddle it hit, from -1 at the
+    /// left end to 1 at the right end. Hits on the sides of the paddle are past either end.
+    BallHitPaddle {
+        offset: f32,
+    },
+
+    /// The sticky paddle caught the ball. It is launched again when released.
+    BallCaught,
+
+    BallHitWall(Wall),
+
+    /// The ball bounced off of the block at `index` in `Breakout::blocks`.
+    BallHitBlock {
+        index: usize,
+        face: Face,
+    },
+
+    /// The block at `index` in `Breakout::blocks` lost a hit point but wasn't destroyed.
+    BlockDamaged {
+        index: usize,
+        hit_points: u32,
+    },
+
+    /// The block at `index` in `Breakout::blocks` was destroyed. `block` is what it was when it
+    /// was removed.
+    BlockDestroyed {
+        index: usize,
+        block: Block,
+        points: u32,
+    },
+
+    /// The explosive block at `index` in `Breakout::blocks` blew up. Every block it destroys is
+    /// reported separately.
+    Explosion {
+        index: usize,
+        center: vec2,
+        radius: f32,
+    },
+
+    /// A destroyed block dropped a capsule, which is now falling.
+    CapsuleDropped(PowerUp),
+
+    /// The paddle caught a capsule and its power-up took effect.
+    PowerUpCaught(PowerUp),
+
+    /// A timed power-up wore off.
+    PowerUpExpired(PowerUp),
+
+    /// The paddle fired a pair of laser projectiles.
+    LaserFired,
+
+    /// A laser projectile hit the block at `index` in `Breakout::blocks`.
+    LaserHitBlock {
+        index: usize,
+    },
+
+    /// One of several balls in play fell out of the bottom of the arena. Play goes on with the
+    /// rest.
+    ExtraBallLost,
+
+    /// The last ball in play fell out of the bottom of the arena.
+    BallLost,
+
+    /// The last ball was lost.
+    GameOver,
+
+    /// The last block was destroyed. `bonus` is how many points clearing it quickly earned.
+    LevelCleared {
+        bonus: u32,
+    },
+}
@@ -0,0 +1,168 @@
+use colors::*;
+use prelude::*;
+
+/// How a `Frame` stores each pixel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// One byte of brightness per pixel.
+    Grayscale,
+
+    /// Three bytes per pixel: red, green and blue.
+    Rgb,
+}
+
+impl PixelFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Grayscale => 1,
+            PixelFormat::Rgb => 3,
+        }
+    }
+}
+
+/// A small picture of a game drawn on the CPU, for agents that learn from pixels and for tests
+/// that compare against saved pictures. It is drawn the way the shaders draw the game: balls are
+/// discs, and everything else is a rectangle.
+///
+/// Rows go from the top of the arena to the bottom. A pixel is covered by a shape when its
+/// middle is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    format: PixelFormat,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, format: PixelFormat) -> Self {
+        assert!(width > 0 && height > 0, "A frame needs at least one pixel");
+        Self {
+            width,
+            height,
+            format,
+            pixels: vec![0; width * height * format.bytes_per_pixel()],
+        }
+    }
+
+    /// Draws `game` in a new frame.
+    pub fn render(game: &Breakout, width: usize, height: usize, format: PixelFormat) -> Self {
+        let mut frame = Self::new(width, height, format);
+        frame.draw(game);
+        frame
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Every pixel, row by row from the top left.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels[..]
+    }
+
+    /// The bytes of the pixel `x` across and `y` down from the top left.
+    pub fn pixel(&self, x: usize, y: usize) -> &[u8] {
+        assert!(
+            x < self.width && y < self.height,
+            "Pixel is outside of the frame"
+        );
+        let bytes = self.format.bytes_per_pixel();
+        let start = (y * self.width + x) * bytes;
+        &self.pixels[start..start + bytes]
+    }
+
+    /// Clears the frame and draws `game` over it, in the same order the window does.
+    pub fn draw(&mut self, game: &Breakout) {
+        let [red, green, blue, _] = CLEAR_COLOR;
+        self.fill([0., 0., 1., 1.], |_, _| true, [red, green, blue]);
+
+        for ball in game.balls() {
+            let [x, y] = ball.location();
+            let radius = ball.radius();
+            self.fill(
+                [x - radius, y - radius, x + radius, y + radius],
+                |px, py| (px - x).powi(2) + (py - y).powi(2) <= radius * radius,
+                BALL_COLOR,
+            );
+        }
+
+        self.fill_rectangle(game.paddle(), PADDLE_COLOR);
+        for block in game.blocks().iter().flatten() {
+            self.fill_rectangle(block, block_color(block));
+        }
+        for projectile in game.projectiles() {
+            self.fill_rectangle(projectile, PROJECTILE_COLOR);
+        }
+        for capsule in game.capsules() {
+            self.fill_rectangle(capsule, capsule_color(capsule.power_up()));
+        }
+    }
+
+    fn fill_rectangle<R: Rectangle>(&mut self, rectangle: &R, color: [f32; 3]) {
+        let (left, right) = (rectangle.left(), rectangle.right());
+        let (bottom, top) = (rectangle.bottom(), rectangle.top());
+        self.fill(
+            [left, bottom, right, top],
+            |x, y| left <= x && x < right && bottom <= y && y < top,
+            color,
+        );
+    }
+
+    /// Colors every pixel whose middle, in game coordinates, is `inside`. Only pixels within
+    /// `bounds`, given as left, bottom, right and top, are looked at.
+    fn fill<F: Fn(f32, f32) -> bool>(&mut self, bounds: [f32; 4], inside: F, color: [f32; 3]) {
+        let value = self.encode(color);
+        let bytes = self.format.bytes_per_pixel();
+        let (width, height) = (self.width as f32, self.height as f32);
+
+        // Pixels a little way past the bounds are included, and left to `inside` to decide.
+        let [left, bottom, right, top] = bounds;
+        let columns = (left * width - 1.).floor().max(0.) as usize
+            ..((right * width + 1.).ceil().max(0.) as usize).min(self.width);
+        let rows = ((1. - top) * height - 1.).floor().max(0.) as usize
+            ..(((1. - bottom) * height + 1.).ceil().max(0.) as usize).min(self.height);
+
+        for row in rows {
+            let y = 1. - (row as f32 + 0.5) / height;
+            for column in columns.clone() {
+                let x = (column as f32 + 0.5) / width;
+                if inside(x, y) {
+                    let start = (row * self.width + column) * bytes;
+                    self.pixels[start..start + bytes].copy_from_slice(&value[..bytes]);
+                }
+            }
+        }
+    }
+
+    fn encode(&self, color: [f32; 3]) -> [u8; 3] {
+        let byte = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+        match self.format {
+            PixelFormat::Grayscale => {
+                // The usual weights for how bright each color looks.
+                let [red, green, blue] = color;
+                [byte(0.299 * red + 0.587 * green + 0.114 * blue), 0, 0]
+            }
+            PixelFormat::Rgb => [byte(color[0]), byte(color[1]), byte(color[2])],
+        }
+    }
+
+    /// The frame as a binary PGM or PPM image, which most image tools can open.
+    pub fn to_pnm(&self) -> Vec<u8> {
+        let magic = match self.format {
+            PixelFormat::Grayscale => "P5",
+            PixelFormat::Rgb => "P6",
+        };
+        let mut image = format!("{}\n{} {}\n255\n", magic, self.width, self.height).into_bytes();
+        image.extend_from_slice(&self.pixels);
+        image
+    }
+}
@@ -2,12 +2,32 @@ use math;
 use prelude::*;
 
 use object::GameObject;
+use projectile::PROJECTILE_SPEED;
+use rand::prng::XorShiftRng;
+use rand::{Rng, SeedableRng};
+use shape;
+use std::collections::VecDeque;
 
+#[derive(Debug, Clone)]
 pub struct BreakoutBuilder {
-    dt: Option<f32>,
-    ball: Option<Ball>,
-    paddle: Option<Paddle>,
-    blocks: Vec<Option<Block>>,
+    pub(crate) dt: Option<f32>,
+    pub(crate) ball: Option<Ball>,
+    pub(crate) paddle: Option<Paddle>,
+    pub(crate) blocks: Vec<Option<Block>>,
+    pub(crate) lives: u32,
+    pub(crate) serve: bool,
+    pub(crate) sticky_paddle: bool,
+    pub(crate) seed: u64,
+    pub(crate) drop_table: DropTable,
+    pub(crate) physics: PhysicsConfig,
+    pub(crate) par_time: Option<f32>,
+    pub(crate) points: u32,
+}
+
+impl Default for BreakoutBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BreakoutBuilder {
@@ -17,6 +37,14 @@ impl BreakoutBuilder {
             ball: None,
             paddle: None,
             blocks: vec![],
+            lives: 3,
+            serve: false,
+            sticky_paddle: false,
+            seed: 0,
+            drop_table: DropTable::empty(),
+            physics: PhysicsConfig::new(),
+            par_time: None,
+            points: 0,
         }
     }
 
@@ -35,6 +63,56 @@ impl BreakoutBuilder {
         self
     }
 
+    /// How many balls the player can lose before the game is over. Defaults to 3.
+    pub fn lives(mut self, lives: u32) -> Self {
+        self.lives = lives;
+        self
+    }
+
+    /// Start the game with the ball resting on the paddle, waiting to be launched, instead of
+    /// already in play. The ball's speed is kept for the launch.
+    pub fn serve(mut self) -> Self {
+        self.serve = true;
+        self
+    }
+
+    /// Makes the paddle catch the ball for the whole game, as if it always had the catch
+    /// power-up.
+    pub fn sticky_paddle(mut self) -> Self {
+        self.sticky_paddle = true;
+        self
+    }
+
+    /// Seeds the randomness in the game, so the same seed and inputs always play out the same.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Decides which power-ups destroyed blocks drop. By default nothing is dropped.
+    pub fn drop_table(mut self, drop_table: DropTable) -> Self {
+        self.drop_table = drop_table;
+        self
+    }
+
+    /// Tunes how the ball and paddle move. Defaults to `PhysicsConfig::new()`.
+    pub fn physics(mut self, physics: PhysicsConfig) -> Self {
+        self.physics = physics;
+        self
+    }
+
+    /// How many seconds the level should take. Clearing it faster earns a bonus.
+    pub fn par_time(mut self, par_time: f32) -> Self {
+        self.par_time = Some(par_time);
+        self
+    }
+
+    /// Points the player already has, e.g. from earlier levels of a campaign.
+    pub fn points(mut self, points: u32) -> Self {
+        self.points = points;
+        self
+    }
+
     pub fn add_block(mut self, block: Block) -> Self {
         self.blocks.push(Some(block));
         self
@@ -45,21 +123,234 @@ impl BreakoutBuilder {
         self
     }
 
+    /// Builds the game, and panics if anything is missing or the level doesn't make sense. Use
+    /// `try_build` for levels that might be bad, like ones made by players.
     pub fn build(self) -> Breakout {
+        match self.try_build() {
+            Ok(game) => game,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Builds the game, or says what is wrong with it.
+    pub fn try_build(self) -> Result<Breakout, BuildError> {
         let BreakoutBuilder {
             dt,
             ball,
             paddle,
             blocks,
+            lives,
+            serve,
+            sticky_paddle,
+            seed,
+            drop_table,
+            physics,
+            par_time,
+            points,
         } = self;
 
-        Breakout {
-            dt: dt.expect("User did not call BreakoutBuilder::dt(f32)"),
-            ball: ball.expect("User did not call BreakoutBuilder::ball(Ball)"),
-            paddle: paddle.expect("User did not call BreakoutBuilder::paddle(Paddle)"),
+        let dt = dt.ok_or(BuildError::MissingDt)?;
+        let ball = ball.ok_or(BuildError::MissingBall)?;
+        let paddle = paddle.ok_or(BuildError::MissingPaddle)?;
+        if lives == 0 {
+            return Err(BuildError::NoLives);
+        }
+        let chance = drop_table.chance();
+        if !(0. ..=1.).contains(&chance) {
+            return Err(BuildError::InvalidDropChance(chance));
+        }
+        if let Some(par_time) = par_time {
+            if !(par_time.is_finite() && par_time >= 0.) {
+                return Err(BuildError::InvalidParTime(par_time));
+            }
+        }
+        validate_physics(&physics)?;
+        validate_level(dt, &ball, &paddle, &blocks, serve)?;
+
+        let mut game = Breakout {
+            dt,
+            balls: vec![ball],
+            held: vec![None],
+            spawn_ball: ball,
+            paddle,
+            spawn_paddle: paddle,
+            sticky_paddle,
             blocks,
+            capsules: vec![],
+            effects: vec![],
+            drop_table,
+            physics,
+            projectiles: vec![],
+            laser_cooldown: 0.,
+            ball_speed_factor: 1.,
+            lives,
+            starting_lives: lives,
+            phase: Phase::Playing,
+            paused_phase: Phase::Playing,
+            score: par_time
+                .map_or_else(Score::new, Score::with_par_time)
+                .starting_at(points),
+            par_time,
+            events: vec![],
+            serve,
+            seed,
+            rng: XorShiftRng::seed_from_u64(seed),
+        };
+
+        if serve {
+            game.serve_ball();
+        }
+
+        // A level with nothing left to destroy is already cleared.
+        game.resolve_level_cleared();
+
+        Ok(game)
+    }
+}
+
+/// Checks that the physics can actually move things. A ball with no top speed would freeze, and
+/// a paddle with no speed couldn't be steered.
+fn validate_physics(physics: &PhysicsConfig) -> Result<(), BuildError> {
+    let positive = |value: f32| value.is_finite() && value > 0.;
+    let checks = [
+        (
+            "paddle_speed",
+            physics.paddle_speed,
+            positive(physics.paddle_speed),
+        ),
+        (
+            "paddle_hit_speedup",
+            physics.paddle_hit_speedup,
+            positive(physics.paddle_hit_speedup),
+        ),
+        (
+            "max_horizontal_fraction",
+            physics.max_horizontal_fraction,
+            (0. ..1.).contains(&physics.max_horizontal_fraction),
+        ),
+        (
+            "max_ball_speed",
+            physics.max_ball_speed,
+            positive(physics.max_ball_speed),
+        ),
+        (
+            "min_vertical_speed",
+            physics.min_vertical_speed,
+            (0. ..physics.max_ball_speed).contains(&physics.min_vertical_speed),
+        ),
+        (
+            "wall_restitution",
+            physics.wall_restitution,
+            positive(physics.wall_restitution) && physics.wall_restitution <= 1.,
+        ),
+    ];
+
+    match checks.iter().find(|&&(_, _, valid)| !valid) {
+        Some(&(name, value, _)) => Err(BuildError::InvalidPhysics { name, value }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that everything in a level is a sensible size and fits where it is.
+fn validate_level(
+    dt: f32,
+    ball: &Ball,
+    paddle: &Paddle,
+    blocks: &[Option<Block>],
+    serve: bool,
+) -> Result<(), BuildError> {
+    if !(dt.is_finite() && dt > 0.) {
+        return Err(BuildError::InvalidDt(dt));
+    }
+
+    let radius = ball.radius();
+    if !(radius.is_finite() && radius > 0.) {
+        return Err(BuildError::InvalidBallRadius(radius));
+    }
+
+    let [vx, vy] = ball.velocity();
+    if !(vx.is_finite() && vy.is_finite()) {
+        return Err(BuildError::InvalidBallVelocity([vx, vy]));
+    }
+    if serve && math::length(ball.velocity()) == 0. {
+        return Err(BuildError::NoLaunchSpeed);
+    }
+
+    let [width, height] = paddle.dimensions();
+    if !(width > 0. && height > 0.) {
+        return Err(BuildError::InvalidPaddleDimensions([width, height]));
+    }
+
+    let blocks: Vec<_> = blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, block)| block.as_ref().map(|block| (index, block)))
+        .collect();
+
+    for &(index, block) in &blocks {
+        let [width, height] = block.dimensions();
+        if !(width > 0. && height > 0.) {
+            return Err(BuildError::InvalidBlockDimensions {
+                index,
+                dimensions: [width, height],
+            });
+        }
+
+        if block.max_hit_points() == 0 {
+            return Err(BuildError::NoHitPoints { index });
+        }
+
+        let valid = |value: f32| value.is_finite() && value >= 0.;
+        let kind = block.kind();
+        match kind {
+            BlockKind::Armored { min_speed: value } | BlockKind::Explosive { radius: value }
+                if !valid(value) =>
+            {
+                return Err(BuildError::InvalidBlockKind { index, kind });
+            }
+            _ => {}
+        }
+    }
+
+    if !math::inside_arena(&ball.bounding_box()) {
+        return Err(BuildError::BallOutsideArena);
+    }
+
+    if !math::inside_arena(&paddle.bounding_box()) {
+        return Err(BuildError::PaddleOutsideArena);
+    }
+
+    for &(index, block) in &blocks {
+        if !math::inside_arena(&block.bounding_box()) {
+            return Err(BuildError::BlockOutsideArena { index });
+        }
+    }
+
+    for (i, &(first, a)) in blocks.iter().enumerate() {
+        for &(second, b) in &blocks[i + 1..] {
+            if math::intersecting_boxes(&a.bounding_box(), &b.bounding_box()) {
+                return Err(BuildError::OverlappingBlocks { first, second });
+            }
+        }
+    }
+
+    // The ball is allowed to start touching things, just not inside of them.
+    let inside = |b: &shape::Box| {
+        math::circle_box_penetration(ball.location(), radius, b)
+            .is_some_and(|(_, depth)| depth > math::EPSILON)
+    };
+
+    for &(index, block) in &blocks {
+        if inside(&block.bounding_box()) {
+            return Err(BuildError::BallInsideBlock { index });
         }
     }
+
+    if inside(&paddle.bounding_box()) {
+        return Err(BuildError::BallInsidePaddle);
+    }
+
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -67,28 +358,171 @@ pub struct Breakout {
     /// This is the time delta the simulation runs at. Each tick occurs exactly once every dt.
     dt: f32,
 
-    /// Holds data for the game Ball
-    ball: Ball,
+    /// Every ball in play. There is always at least one: when the last ball is lost it is
+    /// replaced by a newly served one, or left where it fell if the game is over.
+    balls: Vec<Ball>,
+
+    /// Which of the balls are caught on the paddle, matching up with `balls`.
+    held: Vec<Option<Hold>>,
+
+    /// The ball as it was when the game started. Every served ball has its size and speed.
+    spawn_ball: Ball,
 
     /// The user's paddle.
     paddle: Paddle,
 
+    /// The paddle as it was when the game started, before any power-ups.
+    spawn_paddle: Paddle,
+
+    /// Whether the paddle catches the ball even without the catch power-up.
+    sticky_paddle: bool,
+
     /// The blocks in the game space. Sorted from closest to furthest.
     blocks: Vec<Option<Block>>,
+
+    /// Power-ups falling towards the paddle.
+    capsules: Vec<Capsule>,
+
+    /// Timed power-ups that are currently active.
+    effects: Vec<Effect>,
+
+    drop_table: DropTable,
+
+    physics: PhysicsConfig,
+
+    /// Laser projectiles fired by the paddle.
+    projectiles: Vec<Projectile>,
+
+    /// Seconds until the paddle can fire again.
+    laser_cooldown: f32,
+
+    /// How much the active power-ups have scaled the ball's speed by.
+    ball_speed_factor: f32,
+
+    /// How many more balls the player can lose. The game is over once this reaches 0.
+    lives: u32,
+
+    /// How many lives the player started with.
+    starting_lives: u32,
+
+    /// What the game is currently doing.
+    phase: Phase,
+
+    /// The phase to go back to when the game is resumed.
+    paused_phase: Phase,
+
+    score: Score,
+
+    /// The par time the level asked for, if it set one.
+    par_time: Option<f32>,
+
+    /// Everything that has happened since the last tick was returned.
+    events: Vec<GameEvent>,
+
+    /// Whether the game started with the ball served from the paddle.
+    serve: bool,
+
+    /// What `rng` was seeded with.
+    seed: u64,
+
+    /// Source of all randomness in the game, so that a game can be replayed from its seed.
+    rng: XorShiftRng,
 }
 
+/// The steepest angle from vertical, in radians, that a ball can be launched at by a moving
+/// paddle.
+const MAX_LAUNCH_ANGLE: f32 = 0.5;
+
+/// How much wider the wide paddle power-up makes the paddle.
+const WIDE_PADDLE_SCALE: f32 = 1.5;
+
+/// How much the slow ball power-up scales the ball's speed by.
+const SLOW_BALL_SCALE: f32 = 0.6;
+
+/// How much the big and small ball power-ups scale the ball's radius by.
+const BIG_BALL_SCALE: f32 = 1.5;
+const SMALL_BALL_SCALE: f32 = 0.6;
+
+/// The least time, in seconds, between two shots of the laser.
+const LASER_COOLDOWN: f32 = 0.25;
+
+/// How long, in seconds, the sticky paddle holds onto a ball before letting it go on its own.
+const CATCH_TIMEOUT: f32 = 2.;
+
+/// How much spin, in radians per second, the paddle puts on the ball for every unit per second
+/// it is moving. The ball curves the way the paddle was moving.
+const SPIN_PER_PADDLE_SPEED: f32 = 1.5;
+
+/// How quickly spin wears off. The ball loses about this fraction of its spin every second.
+const SPIN_DECAY: f32 = 1.;
+
+/// How far, in radians, a bounce kicks the ball to one side for every radian per second of spin.
+const SPIN_BOUNCE_ANGLE: f32 = 0.2;
+
+/// The furthest a bounce can kick the ball to one side, no matter how much spin it has.
+const MAX_SPIN_BOUNCE_ANGLE: f32 = 0.3;
+
+/// How much of its spin the ball keeps after bouncing off of a wall or block.
+const SPIN_KEPT_ON_BOUNCE: f32 = 0.5;
+
+/// The angle, in radians, between a split ball and the balls split off of it.
+const SPLIT_ANGLE: f32 = 0.4;
+
+/// The most balls that can be in play at once.
+const MAX_BALLS: usize = 12;
+
+/// When launched from a paddle that is standing still, the ball goes off at a random angle from
+/// vertical of up to this many radians either way.
+const LAUNCH_SPREAD: f32 = 0.15;
+
 impl Breakout {
     pub fn level_1(dt: f32) -> Self {
         BreakoutBuilder::new()
             .dt(dt)
             .ball(Ball::new(0.015, [0.5, 0.7], [0., -0.5]))
             .paddle(Paddle::new([0.15, 0.02], [0.425, 0.065]))
+            .serve()
+            .drop_table(
+                DropTable::new(0.25)
+                    .with(PowerUp::WidePaddle, 3)
+                    .with(PowerUp::SlowBall, 3)
+                    .with(PowerUp::BigBall, 2)
+                    .with(PowerUp::SmallBall, 1)
+                    .with(PowerUp::MultiBall, 2)
+                    .with(PowerUp::Laser, 2)
+                    .with(PowerUp::Catch, 2)
+                    .with(PowerUp::ExtraLife, 1),
+            )
             .add_blocks(
                 (0..4_i32).map(|i| Block::new([0.10, 0.05], [0.2 * (i + 1) as f32 - 0.05, 0.725])),
             )
             .build()
     }
 
+    /// A builder for the level as it stands now: the blocks that are left and the points scored,
+    /// with the ball, paddle and lives back where they started and nothing else changed.
+    pub fn to_builder(&self) -> BreakoutBuilder {
+        BreakoutBuilder {
+            dt: Some(self.dt),
+            ball: Some(self.spawn_ball),
+            paddle: Some(self.spawn_paddle),
+            blocks: self
+                .blocks
+                .iter()
+                .filter(|block| block.is_some())
+                .cloned()
+                .collect(),
+            lives: self.starting_lives,
+            serve: self.serve,
+            sticky_paddle: self.sticky_paddle,
+            seed: self.seed,
+            drop_table: self.drop_table.clone(),
+            physics: self.physics,
+            par_time: self.par_time,
+            points: self.score.points(),
+        }
+    }
+
     pub fn dt(&self) -> f32 {
         self.dt
     }
@@ -101,93 +535,409 @@ impl Breakout {
         &mut self.paddle
     }
 
+    /// The first ball in play.
     pub fn ball(&self) -> &Ball {
-        &self.ball
+        &self.balls[0]
+    }
+
+    pub fn balls(&self) -> &[Ball] {
+        &self.balls[..]
     }
 
     pub fn blocks(&self) -> &[Option<Block>] {
         &self.blocks[..]
     }
 
+    /// Power-ups that are falling towards the paddle.
+    pub fn capsules(&self) -> &[Capsule] {
+        &self.capsules[..]
+    }
+
+    /// Timed power-ups that are currently active.
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects[..]
+    }
+
+    /// Laser projectiles that are in flight.
+    pub fn projectiles(&self) -> &[Projectile] {
+        &self.projectiles[..]
+    }
+
+    /// Whether balls that land on the paddle are caught instead of bouncing off.
+    pub fn paddle_is_sticky(&self) -> bool {
+        self.sticky_paddle || self.has_effect(PowerUp::Catch)
+    }
+
+    /// Whether the ball at `index` in `balls` is caught on the paddle.
+    pub fn ball_is_held(&self, index: usize) -> bool {
+        self.held[index].is_some()
+    }
+
+    /// Whether the paddle can fire lasers.
+    pub fn laser_active(&self) -> bool {
+        self.has_effect(PowerUp::Laser)
+    }
+
+    pub fn physics(&self) -> &PhysicsConfig {
+        &self.physics
+    }
+
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Freezes the game until `resume` is called. Only a game that is serving or playing can be
+    /// paused.
+    pub fn pause(&mut self) {
+        if self.phase == Phase::Serve || self.phase == Phase::Playing {
+            self.paused_phase = self.phase;
+            self.phase = Phase::Paused;
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.phase == Phase::Paused {
+            self.phase = self.paused_phase;
+        }
+    }
+
+    /// Launches the ball off of the paddle when serving, or releases any balls the sticky paddle
+    /// has caught.
+    pub fn launch(&mut self) {
+        match self.phase {
+            Phase::Serve => self.launch_served_ball(),
+            Phase::Playing => {
+                for ball in 0..self.balls.len() {
+                    self.release_ball(ball);
+                }
+            }
+            Phase::Paused | Phase::LevelCleared | Phase::GameOver => {}
+        }
+    }
+
+    /// The served ball goes off in the direction the paddle is moving, or at a small random angle
+    /// if it isn't moving.
+    fn launch_served_ball(&mut self) {
+        let speed = math::length(self.spawn_ball.velocity()) * self.ball_speed_factor;
+        let [paddle_vx, _] = self.paddle.velocity();
+
+        let angle = if paddle_vx != 0. {
+            (paddle_vx / speed)
+                .atan()
+                .clamp(-MAX_LAUNCH_ANGLE, MAX_LAUNCH_ANGLE)
+        } else {
+            self.rng.gen_range(-LAUNCH_SPREAD, LAUNCH_SPREAD)
+        };
+
+        self.balls[0].set_velocity([speed * angle.sin(), speed * angle.cos()]);
+        self.phase = Phase::Playing;
+        self.events.push(GameEvent::BallLaunched);
+    }
+
+    /// Splits every ball in play into three, with the two new balls going off at an angle to
+    /// either side of the original. Only moving balls can be split, so this does nothing to a
+    /// ball that is being served or is caught on the paddle, and no more than `MAX_BALLS` are
+    /// ever in play at once.
+    pub fn split_balls(&mut self) {
+        if self.phase != Phase::Playing {
+            return;
+        }
+
+        for i in 0..self.balls.len() {
+            if self.held[i].is_some() {
+                continue;
+            }
+
+            for &angle in &[SPLIT_ANGLE, -SPLIT_ANGLE] {
+                if self.balls.len() >= MAX_BALLS {
+                    return;
+                }
+
+                let mut ball = self.balls[i];
+                ball.set_velocity(math::rotate(ball.velocity(), angle));
+                self.balls.push(ball);
+                self.held.push(None);
+            }
+        }
+    }
+
+    /// Moves the paddle sideways. `direction` goes from -1 for full speed to the left to 1 for
+    /// full speed to the right, and 0 stops the paddle.
+    pub fn steer_paddle(&mut self, direction: f32) {
+        let speed = direction.clamp(-1., 1.) * self.physics.paddle_speed;
+        self.paddle.set_velocity([speed, 0.]);
+    }
+
+    /// Fires a projectile from each end of the paddle, if the laser is active and has cooled
+    /// down since the last shot.
+    pub fn fire(&mut self) {
+        if self.phase != Phase::Playing || !self.laser_active() || self.laser_cooldown > 0. {
+            return;
+        }
+
+        let y = self.paddle.top();
+        let [width, _] = Projectile::new([0., 0.]).dimensions();
+        for &x in &[self.paddle.left() + width, self.paddle.right() - width] {
+            self.projectiles.push(Projectile::new([x, y]));
+        }
+
+        self.laser_cooldown = LASER_COOLDOWN;
+        self.events.push(GameEvent::LaserFired);
+    }
+
+    /// Does what the player asked for on this tick. Call it once before every `tick` to make the
+    /// game depend only on its starting state and the commands it was given.
+    pub fn apply(&mut self, command: Command) {
+        self.steer_paddle(command.steer);
+
+        if command.pause {
+            if self.phase == Phase::Paused {
+                self.resume();
+            } else {
+                self.pause();
+            }
+        }
+        if command.launch {
+            self.launch();
+        }
+        if command.fire {
+            self.fire();
+        }
+    }
+
     fn tick_positions(&mut self) {
-        math::tick_position(&mut self.ball, self.dt);
         math::tick_position(&mut self.paddle, self.dt);
     }
 
-    fn resolve_ball_collisions(&mut self) {
-        let [ball_x, ball_y] = self.ball.location();
-        let ball_r = self.ball.radius();
+    /// Finds the first thing the ball touches while moving by `displacement`, if anything.
+    fn next_ball_contact(
+        &self,
+        ball: usize,
+        displacement: vec2,
+    ) -> Option<(Collider, math::Contact)> {
+        let center = self.balls[ball].location();
+        let [ball_x, ball_y] = center;
+        let ball_r = self.balls[ball].radius();
+        let [dx, dy] = displacement;
+
+        let mut nearest: Option<(Collider, math::Contact)> = None;
+        let mut consider = |collider: Collider, contact: Option<math::Contact>| {
+            if let Some(contact) = contact {
+                if nearest.is_none_or(|(_, n)| contact.time < n.time) {
+                    nearest = Some((collider, contact));
+                }
+            }
+        };
 
-        // Check for collisions and make corrections
-        if math::objects_are_close(&self.ball, &self.paddle) {
-            let (paddle_left, paddle_top, paddle_right, paddle_bottom) = (
-                self.paddle().left(),
-                self.paddle().top(),
-                self.paddle().right(),
-                self.paddle().bottom(),
+        // The left, right and top of the screen. The bottom is left open.
+        if dx < 0. {
+            consider(
+                Collider::Wall(Wall::Left),
+                wall_contact(ball_x - ball_r, -dx, [1., 0.]),
             );
-            let paddle_mid = (paddle_left + paddle_right) * 0.5;
-            let [paddle_len, _] = self.paddle.dimensions();
+        }
+        if dx > 0. {
+            consider(
+                Collider::Wall(Wall::Right),
+                wall_contact(1. - ball_x - ball_r, dx, [-1., 0.]),
+            );
+        }
+        if dy > 0. {
+            consider(
+                Collider::Wall(Wall::Top),
+                wall_contact(1. - ball_y - ball_r, dy, [0., -1.]),
+            );
+        }
 
-            // Easy, but non-exhaustive check
-            if ball_x >= paddle_left && ball_x <= paddle_right {
-                if ball_y - ball_r <= paddle_top && ball_y + ball_r >= paddle_bottom {
-                    // Move ball back above paddle by amount it "dipped" into it and reverse y
-                    // component of velocity. This needs a more exhaustive and correct
-                    // implementation.
-                    let dip = paddle_top - (ball_y - ball_r);
-                    self.ball.set_location([ball_x, paddle_top + dip + ball_r]);
+        // Only do the exact test on objects that are anywhere near the ball's path.
+        let swept = math::swept_bounding_box(center, ball_r, displacement);
 
-                    let [ball_vx, ball_vy] = self.ball.velocity();
+        let paddle_box = self.paddle.bounding_box();
+        if math::overlapping_boxes(&swept, &paddle_box) {
+            consider(
+                Collider::Paddle,
+                math::sweep_circle_box(center, ball_r, displacement, &paddle_box),
+            );
+        }
 
-                    let speed = (ball_vx * ball_vx + ball_vy * ball_vy).sqrt() * 1.05;
+        for (i, block) in self.blocks.iter().enumerate() {
+            if let Some(block) = block {
+                let block_box = block.bounding_box();
+                if math::overlapping_boxes(&swept, &block_box) {
+                    consider(
+                        Collider::Block(i),
+                        math::sweep_circle_box(center, ball_r, displacement, &block_box),
+                    );
+                }
+            }
+        }
 
-                    let offset_from_paddle_center = ball_x - paddle_mid;
+        nearest
+    }
 
-                    // will be negative when on the left side of paddle.
-                    let percent_from_paddle_center = offset_from_paddle_center / (paddle_len * 0.5);
+    /// The paddle pushes the ball along with it when it is moving into the ball faster than the
+    /// ball is moving away.
+    fn carried_by_paddle(&self, velocity: vec2, normal: vec2) -> vec2 {
+        let paddle_speed = math::dot(self.paddle.velocity(), normal);
+        let ball_speed = math::dot(velocity, normal);
 
-                    // limit the maximum x component
-                    let percent_speed_in_x_direction = percent_from_paddle_center * 0.8;
+        if paddle_speed > ball_speed {
+            math::add(velocity, math::scale(normal, paddle_speed - ball_speed))
+        } else {
+            velocity
+        }
+    }
 
-                    let new_ball_vx = percent_speed_in_x_direction * speed;
-                    let new_ball_vy = (speed * speed - new_ball_vx * new_ball_vx).sqrt();
+    /// How far along the paddle `ball_x` is, from -1 at the left end to 1 at the right end.
+    fn paddle_offset(&self, ball_x: f32) -> f32 {
+        let (paddle_left, paddle_right) = (self.paddle().left(), self.paddle().right());
+        let paddle_mid = (paddle_left + paddle_right) * 0.5;
+        let [paddle_len, _] = self.paddle.dimensions();
 
-                    self.ball.set_velocity([new_ball_vx, new_ball_vy]);
-                }
-            }
+        let offset_from_paddle_center = ball_x - paddle_mid;
+
+        // will be negative when on the left side of paddle.
+        offset_from_paddle_center / (paddle_len * 0.5)
+    }
+
+    /// The velocity a ball at `ball_x` leaves the top of the paddle with. The further from the
+    /// middle of the paddle it is, the further off to that side it goes.
+    fn paddle_rebound(&self, ball_x: f32, speed: f32) -> vec2 {
+        // limit the maximum x component
+        let percent_speed_in_x_direction =
+            self.paddle_offset(ball_x) * self.physics.max_horizontal_fraction;
+
+        let new_ball_vx = percent_speed_in_x_direction * speed;
+        let new_ball_vy = (speed * speed - new_ball_vx * new_ball_vx).sqrt();
+
+        [new_ball_vx, new_ball_vy]
+    }
+
+    fn resolve_ball_paddle_collision(&mut self, ball: usize, contact: math::Contact) {
+        let [ball_x, _] = self.balls[ball].location();
+        let percent_from_paddle_center = self.paddle_offset(ball_x);
+
+        self.score.paddle_hit();
+        self.events.push(GameEvent::BallHitPaddle {
+            offset: percent_from_paddle_center,
+        });
+
+        // A moving paddle puts spin on the ball, which curves it off the way the paddle was going.
+        let [paddle_vx, _] = self.paddle.velocity();
+        self.balls[ball].set_spin(-paddle_vx * SPIN_PER_PADDLE_SPEED);
+
+        let [ball_vx, ball_vy] = self.balls[ball].velocity();
+
+        if Face::from_normal(contact.normal) != Face::Top {
+            // The sides, corners and underside of the paddle bounce the ball like a wall would,
+            // so a ball clipping a corner comes off at an angle that follows the corner's curve.
+            let velocity = math::reflect([ball_vx, ball_vy], contact.normal);
+            let velocity = self.carried_by_paddle(velocity, contact.normal);
+            self.balls[ball].set_velocity(velocity);
+            return;
         }
 
-        let [ball_x, ball_y] = self.ball.location();
+        let speed =
+            (ball_vx * ball_vx + ball_vy * ball_vy).sqrt() * self.physics.paddle_hit_speedup;
 
-        // Check if hits top of screen
-        if ball_y + ball_r >= 1.0 {
-            let passed = ball_y + ball_r - 1.0;
-            self.ball.set_location([ball_x, 1.0 - passed - ball_r]);
+        if self.paddle_is_sticky() {
+            // The ball goes off the same way it would have bounced once it is released.
+            self.held[ball] = Some(Hold {
+                offset: ball_x - self.paddle.left(),
+                speed,
+                remaining: CATCH_TIMEOUT,
+            });
+            self.balls[ball].set_velocity([0., 0.]);
+            self.events.push(GameEvent::BallCaught);
+            return;
+        }
 
-            let [ball_vx, ball_vy] = self.ball.velocity();
-            self.ball.set_velocity([ball_vx, -ball_vy]);
+        let velocity = self.paddle_rebound(ball_x, speed);
+        self.balls[ball].set_velocity(velocity);
+    }
+
+    /// Lets go of the ball at `ball` if the paddle has caught it.
+    fn release_ball(&mut self, ball: usize) {
+        if let Some(hold) = self.held[ball].take() {
+            let [ball_x, _] = self.balls[ball].location();
+            let velocity = self.paddle_rebound(ball_x, hold.speed);
+            self.balls[ball].set_velocity(velocity);
+            self.limit_ball_velocity(ball);
+            self.events.push(GameEvent::BallLaunched);
         }
+    }
 
-        let [ball_x, ball_y] = self.ball.location();
+    /// Keeps caught balls where they landed on the paddle as it moves, and releases any that
+    /// have been held for too long.
+    fn tick_held_balls(&mut self) {
+        for ball in 0..self.balls.len() {
+            let expired = match self.held[ball] {
+                Some(ref mut hold) => {
+                    hold.remaining -= self.dt;
+                    hold.remaining <= 0.
+                }
+                None => continue,
+            };
 
-        // left side of screen
-        if ball_x - ball_r <= 0.0 {
-            self.ball.set_location([ball_r - ball_x, ball_y]);
+            // The paddle may have shrunk since the ball was caught.
+            let [paddle_len, _] = self.paddle.dimensions();
+            let offset = self.held[ball].unwrap().offset.min(paddle_len);
+            let ball_y = self.paddle.top() + self.balls[ball].radius();
+            self.balls[ball].set_location([self.paddle.left() + offset, ball_y]);
 
-            let [ball_vx, ball_vy] = self.ball.velocity();
-            self.ball.set_velocity([-ball_vx, ball_vy]);
+            if expired {
+                self.release_ball(ball);
+            }
         }
+    }
 
-        let [ball_x, ball_y] = self.ball.location();
+    /// The paddle moves before the ball does, so a paddle running into the ball from the side can
+    /// end up on top of it. Push the ball out of the way, unless it is pinned against a wall, in
+    /// which case the paddle gets stopped short instead.
+    fn resolve_paddle_ball_overlap(&mut self, ball: usize) {
+        let ball_r = self.balls[ball].radius();
+        let (normal, depth) = match math::circle_box_penetration(
+            self.balls[ball].location(),
+            ball_r,
+            &self.paddle.bounding_box(),
+        ) {
+            Some(penetration) => penetration,
+            None => return,
+        };
+
+        let [ball_x, ball_y] = math::add(self.balls[ball].location(), math::scale(normal, depth));
+
+        // How far past the walls pushing the ball out would put it.
+        let squeezed = if ball_x - ball_r < 0. {
+            ball_x - ball_r
+        } else if ball_x + ball_r > 1. {
+            ball_x + ball_r - 1.
+        } else {
+            0.
+        };
+
+        if squeezed != 0. {
+            let [paddle_x, paddle_y] = self.paddle.location();
+            self.paddle.set_location([paddle_x - squeezed, paddle_y]);
+            self.paddle.set_velocity([0., 0.]);
+        }
 
-        // right side of screen
-        if ball_x + ball_r >= 1.0 {
-            let passed = ball_x + ball_r - 1.0;
-            self.ball.set_location([1.0 - passed - ball_r, ball_y]);
+        self.balls[ball].set_location([ball_x - squeezed, ball_y]);
 
-            let [ball_vx, ball_vy] = self.ball.velocity();
-            self.ball.set_velocity([-ball_vx, ball_vy]);
+        let velocity = self.balls[ball].velocity();
+        if math::dot(velocity, normal) < 0. {
+            self.resolve_ball_paddle_collision(ball, math::Contact { time: 0., normal });
+        } else {
+            let velocity = self.carried_by_paddle(velocity, normal);
+            self.balls[ball].set_velocity(velocity);
         }
     }
 
@@ -211,25 +961,565 @@ impl Breakout {
         }
     }
 
-    fn resolve_ball_block_collisions(&mut self) {
-        for block in &mut self.blocks {
-            if block.is_some() {
-                // Check for collisions and make corrections
-                if math::objects_are_close(&self.ball, block.as_ref().unwrap()) {
-                    *block = None;
+    /// Takes a hit point off of the block at `index` for a hit from a ball moving at `speed`,
+    /// and destroys it if that was its last.
+    fn damage_block(&mut self, index: usize, speed: f32) {
+        let mut explosions = VecDeque::new();
+        self.damage_single_block(index, speed, &mut explosions);
+
+        // Explosions are set off in the order their blocks were destroyed, and each one damages
+        // blocks in index order, so the same hit always plays out the same chain reaction.
+        while let Some((center, radius)) = explosions.pop_front() {
+            for i in 0..self.blocks.len() {
+                let in_range = match self.blocks[i] {
+                    Some(ref block) => {
+                        math::circle_box_penetration(center, radius, &block.bounding_box())
+                            .is_some()
+                    }
+                    None => false,
+                };
+
+                if in_range {
+                    // Nothing is tough enough to shrug off an explosion, other than steel.
+                    self.damage_single_block(i, f32::INFINITY, &mut explosions);
                 }
             }
         }
     }
 
-    fn resolve_collisions(&mut self) {
-        self.resolve_ball_collisions();
-        self.resolve_paddle_collisions();
-        self.resolve_ball_block_collisions();
+    /// Damages just the block at `index`. If it is an explosive block and is destroyed, its
+    /// explosion is added to `explosions` to be set off afterwards.
+    fn damage_single_block(
+        &mut self,
+        index: usize,
+        speed: f32,
+        explosions: &mut VecDeque<(vec2, f32)>,
+    ) {
+        let block = match self.blocks[index] {
+            Some(ref mut block) => block,
+            None => return,
+        };
+
+        if !block.hit(speed) {
+            return;
+        }
+
+        if !block.is_destroyed() {
+            let hit_points = block.hit_points();
+            self.events
+                .push(GameEvent::BlockDamaged { index, hit_points });
+            return;
+        }
+
+        let block = *block;
+        self.blocks[index] = None;
+
+        let points = self.score.block_destroyed(block.points());
+        self.events.push(GameEvent::BlockDestroyed {
+            index,
+            block,
+            points,
+        });
+
+        if let Some(power_up) = self.drop_table.roll(&mut self.rng) {
+            self.capsules.push(Capsule::new(power_up, block.center()));
+            self.events.push(GameEvent::CapsuleDropped(power_up));
+        }
+
+        if let BlockKind::Explosive { radius } = block.kind() {
+            let center = block.center();
+            self.events.push(GameEvent::Explosion {
+                index,
+                center,
+                radius,
+            });
+            explosions.push_back((center, radius));
+        }
+    }
+
+    /// Ends the level once every block that can be destroyed has been.
+    fn resolve_level_cleared(&mut self) {
+        if self.phase != Phase::Playing && self.phase != Phase::Serve {
+            return;
+        }
+
+        if self
+            .blocks
+            .iter()
+            .flatten()
+            .all(|block| !block.is_destructible())
+        {
+            let bonus = self.score.level_cleared();
+            self.events.push(GameEvent::LevelCleared { bonus });
+            self.phase = Phase::LevelCleared;
+        }
+    }
+
+    /// Bounces the ball off of the block it touched. Only the first block the ball touches in a
+    /// tick is damaged, anything else it runs into afterwards just deflects it.
+    fn resolve_ball_block_collision(
+        &mut self,
+        ball: usize,
+        index: usize,
+        contact: math::Contact,
+        damage: bool,
+    ) {
+        let [ball_vx, ball_vy] = self.balls[ball].velocity();
+        let speed = math::length([ball_vx, ball_vy]);
+        let face = Face::from_normal(contact.normal);
+        self.events.push(GameEvent::BallHitBlock { index, face });
+
+        let velocity = match face {
+            Face::Top | Face::Bottom => [ball_vx, -ball_vy],
+            Face::Left | Face::Right => [-ball_vx, ball_vy],
+            Face::Corner => math::reflect([ball_vx, ball_vy], contact.normal),
+        };
+        self.balls[ball].set_velocity(velocity);
+        self.spin_bounce(ball, contact.normal);
+
+        if damage {
+            self.damage_block(index, speed);
+            self.resolve_level_cleared();
+        }
+    }
+
+    /// A spinning ball kicks off to one side when it bounces off of `normal`, and the bounce
+    /// takes some of its spin away.
+    fn spin_bounce(&mut self, ball: usize, normal: vec2) {
+        let ball = &mut self.balls[ball];
+        let spin = ball.spin();
+
+        let angle = (spin * SPIN_BOUNCE_ANGLE).clamp(-MAX_SPIN_BOUNCE_ANGLE, MAX_SPIN_BOUNCE_ANGLE);
+        let velocity = math::rotate(ball.velocity(), angle);
+
+        // A glancing bounce can't be kicked back into what the ball bounced off of.
+        if math::dot(velocity, normal) > 0. {
+            ball.set_velocity(velocity);
+        }
+        ball.set_spin(spin * SPIN_KEPT_ON_BOUNCE);
+    }
+
+    /// Spin turns the ball's path a little every tick, and wears off over time.
+    fn curve_ball(&mut self, ball: usize) {
+        let dt = self.dt;
+        let ball = &mut self.balls[ball];
+        let spin = ball.spin();
+
+        let velocity = math::rotate(ball.velocity(), spin * dt);
+        ball.set_velocity(velocity);
+        ball.set_spin(spin * (-SPIN_DECAY * dt).exp());
     }
 
-    pub fn tick(&mut self) {
-        self.tick_positions();
-        self.resolve_collisions();
+    fn resolve_ball_collision(
+        &mut self,
+        ball: usize,
+        collider: Collider,
+        contact: math::Contact,
+        hit_block: &mut bool,
+    ) {
+        match collider {
+            Collider::Wall(wall) => {
+                let velocity = math::reflect(self.balls[ball].velocity(), contact.normal);
+
+                // Walls can soak up some of the ball's speed into them.
+                let rebound = math::dot(velocity, contact.normal);
+                let velocity = math::add(
+                    velocity,
+                    math::scale(
+                        contact.normal,
+                        rebound * (self.physics.wall_restitution - 1.),
+                    ),
+                );
+                self.balls[ball].set_velocity(velocity);
+                self.spin_bounce(ball, contact.normal);
+                self.events.push(GameEvent::BallHitWall(wall));
+            }
+            Collider::Paddle => self.resolve_ball_paddle_collision(ball, contact),
+            Collider::Block(index) => {
+                self.resolve_ball_block_collision(ball, index, contact, !*hit_block);
+                *hit_block = true;
+            }
+        }
+
+        self.limit_ball_velocity(ball);
     }
+
+    /// Keeps a ball that just bounced under the speed limit, and moving up or down fast enough
+    /// that it doesn't spend forever crossing the arena.
+    fn limit_ball_velocity(&mut self, ball: usize) {
+        let ball = &mut self.balls[ball];
+        let velocity = ball.velocity();
+        let speed = math::length(velocity);
+        if speed == 0. {
+            return;
+        }
+
+        let capped = speed.min(self.physics.max_ball_speed);
+        let [vx, vy] = math::scale(velocity, capped / speed);
+        let speed = capped;
+
+        let min_vy = self.physics.min_vertical_speed.min(speed);
+        let vy = if vy.abs() < min_vy {
+            min_vy.copysign(vy)
+        } else {
+            vy
+        };
+        let vx = (speed * speed - vy * vy).max(0.).sqrt().copysign(vx);
+
+        ball.set_velocity([vx, vy]);
+    }
+
+    /// Moves a ball through the tick, stopping at everything it touches along the way so that a
+    /// fast ball can't skip over anything between ticks.
+    fn tick_ball(&mut self, ball: usize) {
+        self.curve_ball(ball);
+
+        let mut remaining = self.dt;
+        let mut hit_block = false;
+
+        for _ in 0..MAX_BALL_CONTACTS_PER_TICK {
+            let displacement = math::scale(self.balls[ball].velocity(), remaining);
+
+            match self.next_ball_contact(ball, displacement) {
+                Some((collider, contact)) => {
+                    let location = math::add(
+                        self.balls[ball].location(),
+                        math::scale(displacement, contact.time),
+                    );
+                    self.balls[ball].set_location(location);
+                    remaining *= 1. - contact.time;
+
+                    self.resolve_ball_collision(ball, collider, contact, &mut hit_block);
+
+                    if self.held[ball].is_some() {
+                        return;
+                    }
+                }
+                None => {
+                    let location = math::add(self.balls[ball].location(), displacement);
+                    self.balls[ball].set_location(location);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Flies the projectiles upward. Each one damages the first block in its path and is used
+    /// up, and any that reach the top of the arena are removed.
+    fn tick_projectiles(&mut self) {
+        let dt = self.dt;
+        self.laser_cooldown = (self.laser_cooldown - dt).max(0.);
+
+        let blocks = &self.blocks;
+        let mut hits = vec![];
+
+        self.projectiles.retain_mut(|projectile| {
+            let mut swept = projectile.bounding_box();
+            swept.top += PROJECTILE_SPEED * dt;
+
+            // The lowest block in the way is the first one the projectile reaches.
+            let hit = blocks
+                .iter()
+                .enumerate()
+                .filter_map(|(i, block)| block.as_ref().map(|block| (i, block)))
+                .filter(|(_, block)| math::overlapping_boxes(&swept, &block.bounding_box()))
+                .min_by(|(_, a), (_, b)| a.bottom().total_cmp(&b.bottom()));
+
+            if let Some((index, _)) = hit {
+                hits.push(index);
+                return false;
+            }
+
+            math::tick_position(projectile, dt);
+            projectile.bottom() < 1.
+        });
+
+        for index in hits {
+            // Two projectiles can hit the same block in one tick.
+            if self.blocks[index].is_none() {
+                continue;
+            }
+
+            self.events.push(GameEvent::LaserHitBlock { index });
+            self.damage_block(index, PROJECTILE_SPEED);
+            self.resolve_level_cleared();
+        }
+    }
+
+    /// Drops the falling capsules, catching any that land on the paddle and removing any that
+    /// fall out of the arena.
+    fn tick_capsules(&mut self) {
+        let paddle_box = self.paddle.bounding_box();
+        let mut caught = vec![];
+
+        for capsule in &mut self.capsules {
+            math::tick_position(capsule, self.dt);
+        }
+
+        self.capsules.retain(|capsule| {
+            if math::overlapping_boxes(&capsule.bounding_box(), &paddle_box) {
+                caught.push(capsule.power_up());
+                false
+            } else {
+                capsule.top() >= 0.
+            }
+        });
+
+        for power_up in caught {
+            self.catch_power_up(power_up);
+        }
+    }
+
+    fn catch_power_up(&mut self, power_up: PowerUp) {
+        self.events.push(GameEvent::PowerUpCaught(power_up));
+
+        let duration = match power_up.duration() {
+            Some(duration) => duration,
+            None => {
+                match power_up {
+                    PowerUp::ExtraLife => self.lives += 1,
+                    PowerUp::MultiBall => self.split_balls(),
+                    _ => (),
+                }
+                return;
+            }
+        };
+
+        if let Some(cancelled) = power_up.cancels() {
+            self.effects.retain(|effect| effect.power_up() != cancelled);
+        }
+
+        match self
+            .effects
+            .iter_mut()
+            .find(|effect| effect.power_up() == power_up)
+        {
+            Some(effect) => effect.restart(duration),
+            None => self.effects.push(Effect::new(power_up, duration)),
+        }
+
+        self.apply_effects();
+    }
+
+    /// Runs down the timers on the active power-ups, and ends any that have worn off.
+    fn tick_effects(&mut self) {
+        let dt = self.dt;
+        let mut expired = vec![];
+
+        self.effects.retain_mut(|effect| {
+            if effect.tick(dt) {
+                expired.push(effect.power_up());
+                false
+            } else {
+                true
+            }
+        });
+
+        if expired.is_empty() {
+            return;
+        }
+
+        for power_up in expired {
+            self.events.push(GameEvent::PowerUpExpired(power_up));
+        }
+        self.apply_effects();
+    }
+
+    fn has_effect(&self, power_up: PowerUp) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.power_up() == power_up)
+    }
+
+    /// Sizes the paddle and ball, and sets the ball's speed, for whichever power-ups are active.
+    fn apply_effects(&mut self) {
+        let [base_width, _] = self.spawn_paddle.dimensions();
+        let width = if self.has_effect(PowerUp::WidePaddle) {
+            base_width * WIDE_PADDLE_SCALE
+        } else {
+            base_width
+        };
+
+        // The paddle grows and shrinks around its center, but never past the walls.
+        let [old_width, height] = self.paddle.dimensions();
+        if width != old_width {
+            let [paddle_x, paddle_y] = self.paddle.location();
+            let paddle_x = (paddle_x + (old_width - width) * 0.5).clamp(0., 1. - width);
+            self.paddle.set_dimensions([width, height]);
+            self.paddle.set_location([paddle_x, paddle_y]);
+        }
+
+        let radius = self.spawn_ball.radius()
+            * if self.has_effect(PowerUp::BigBall) {
+                BIG_BALL_SCALE
+            } else if self.has_effect(PowerUp::SmallBall) {
+                SMALL_BALL_SCALE
+            } else {
+                1.
+            };
+        let speed_factor = if self.has_effect(PowerUp::SlowBall) {
+            SLOW_BALL_SCALE
+        } else {
+            1.
+        };
+        let speed_change = speed_factor / self.ball_speed_factor;
+        self.ball_speed_factor = speed_factor;
+
+        for hold in self.held.iter_mut().flatten() {
+            hold.speed *= speed_change;
+        }
+
+        for ball in &mut self.balls {
+            ball.set_radius(radius);
+
+            let velocity = ball.velocity();
+            ball.set_velocity(math::scale(velocity, speed_change));
+        }
+    }
+
+    /// Removes any balls that have fallen completely out of the bottom of the arena. Once the
+    /// last one is gone, a new ball is served if the player has any lives left.
+    fn resolve_balls_lost(&mut self) {
+        if self.phase != Phase::Playing {
+            return;
+        }
+
+        // The last ball to fall is kept where it is, so there is always a ball to show. If every
+        // ball falls out on this tick, that is the one that is highest up.
+        let last = *self
+            .balls
+            .iter()
+            .max_by(|a, b| a.location().y().total_cmp(&b.location().y()))
+            .unwrap();
+        let before = self.balls.len();
+        let (balls, held) = self
+            .balls
+            .iter()
+            .cloned()
+            .zip(self.held.iter().cloned())
+            .filter(|(ball, _)| ball.location().y() + ball.radius() >= 0.)
+            .unzip();
+        self.balls = balls;
+        self.held = held;
+
+        if !self.balls.is_empty() {
+            for _ in self.balls.len()..before {
+                self.events.push(GameEvent::ExtraBallLost);
+            }
+            return;
+        }
+        self.balls.push(last);
+
+        self.score.ball_lost();
+        self.events.push(GameEvent::BallLost);
+
+        // Losing the ball loses every power-up too.
+        self.capsules.clear();
+        self.projectiles.clear();
+        self.effects.clear();
+        self.apply_effects();
+
+        // A game can be built with no lives to spare, and then the first ball lost ends it.
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives == 0 {
+            self.phase = Phase::GameOver;
+            self.events.push(GameEvent::GameOver);
+            return;
+        }
+
+        self.serve_ball();
+    }
+
+    /// Puts a new ball on the paddle and waits for it to be launched.
+    fn serve_ball(&mut self) {
+        let mut ball = self.spawn_ball;
+        ball.set_velocity([0., 0.]);
+        self.balls = vec![ball];
+        self.held = vec![None];
+        self.phase = Phase::Serve;
+        self.hold_ball_on_paddle();
+    }
+
+    /// Keeps a served ball sitting on the middle of the paddle as it moves.
+    fn hold_ball_on_paddle(&mut self) {
+        let paddle_mid = (self.paddle.left() + self.paddle.right()) * 0.5;
+        let ball_y = self.paddle.top() + self.balls[0].radius();
+        self.balls[0].set_location([paddle_mid, ball_y]);
+    }
+
+    /// Advances the game by dt and returns everything that happened along the way. Anything that
+    /// happened between ticks, like launching the ball, is returned by the next tick.
+    pub fn tick(&mut self) -> Vec<GameEvent> {
+        match self.phase {
+            Phase::Serve => {
+                self.score.tick(self.dt);
+                self.tick_positions();
+                self.resolve_paddle_collisions();
+                self.hold_ball_on_paddle();
+            }
+            Phase::Playing => {
+                self.score.tick(self.dt);
+                self.tick_positions();
+                self.resolve_paddle_collisions();
+                self.tick_held_balls();
+                for ball in 0..self.balls.len() {
+                    if self.held[ball].is_some() {
+                        continue;
+                    }
+
+                    self.resolve_paddle_ball_overlap(ball);
+                    self.tick_ball(ball);
+
+                    // Stop as soon as one of the balls clears the level.
+                    if self.phase != Phase::Playing {
+                        break;
+                    }
+                }
+                self.tick_projectiles();
+                self.tick_capsules();
+                self.tick_effects();
+                self.resolve_balls_lost();
+            }
+            Phase::Paused | Phase::LevelCleared | Phase::GameOver => {}
+        }
+        self.resolve_level_cleared();
+
+        ::std::mem::take(&mut self.events)
+    }
+}
+
+/// The most contacts the ball resolves in a single tick. A ball wedged between two objects would
+/// otherwise bounce back and forth forever.
+const MAX_BALL_CONTACTS_PER_TICK: usize = 8;
+
+/// Everything the ball can run into.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Collider {
+    Wall(Wall),
+    Paddle,
+    Block(usize),
+}
+
+/// Contact with a wall that is `gap` away from the edge of the ball, when the ball is moving
+/// `approach` towards it.
+fn wall_contact(gap: f32, approach: f32, normal: vec2) -> Option<math::Contact> {
+    let time = (gap / approach).max(0.);
+    if time <= 1. {
+        Some(math::Contact { time, normal })
+    } else {
+        None
+    }
+}
+
+/// A ball caught by the sticky paddle.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Hold {
+    /// How far from the left end of the paddle the ball landed.
+    offset: f32,
+
+    /// How fast the ball goes once it is released.
+    speed: f32,
+
+    /// Seconds until the ball is released on its own.
+    remaining: f32,
 }
@@ -0,0 +1,274 @@
+use prelude::*;
+use rand::prng::XorShiftRng;
+use rand::{Rng, SeedableRng};
+
+/// Blocks are laid out between these two x coordinates.
+const GRID_LEFT: f32 = 0.05;
+const GRID_RIGHT: f32 = 0.95;
+
+/// Where the top row of blocks starts.
+const GRID_TOP: f32 = 0.9;
+
+const ROW_HEIGHT: f32 = 0.04;
+
+/// Any more rows than this and the blocks would come down to where the ball starts.
+const MAX_ROWS: u32 = 12;
+
+/// Seconds of par time given for every block that can be destroyed.
+const PAR_TIME_PER_BLOCK: f32 = 3.;
+
+/// How the blocks of a generated level mirror each other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+
+    /// The right half mirrors the left half.
+    Mirror,
+
+    /// The right half mirrors the left half, and the bottom rows mirror the top ones.
+    Quad,
+}
+
+/// How the chance of a cell having a block changes from the top row to the bottom row.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DensityCurve {
+    Flat,
+
+    /// Full at the top and thinning out towards the bottom.
+    Top,
+
+    /// Thin at the top and filling up towards the bottom.
+    Bottom,
+
+    /// Fullest in the middle rows.
+    Middle,
+}
+
+impl DensityCurve {
+    /// How much of the level's density a row gets, where `row` goes from 0 at the top to 1 at
+    /// the bottom.
+    fn weight(self, row: f32) -> f32 {
+        match self {
+            DensityCurve::Flat => 1.,
+            DensityCurve::Top => 1. - 0.7 * row,
+            DensityCurve::Bottom => 0.3 + 0.7 * row,
+            DensityCurve::Middle => 1. - 1.4 * (row - 0.5).abs(),
+        }
+    }
+}
+
+/// Makes levels from a seed. The same settings and seed always make the same level.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Generator {
+    pub columns: u32,
+
+    /// At most 12.
+    pub rows: u32,
+
+    /// From 0 to 1. Harder levels have tougher blocks, a faster ball, a smaller paddle and fewer
+    /// power-ups.
+    pub difficulty: f32,
+
+    /// The chance of a cell having a block, from 0 to 1, before the density curve is applied.
+    pub density: f32,
+    pub density_curve: DensityCurve,
+    pub symmetry: Symmetry,
+
+    /// The chance of a whole column being left empty, from 0 to 1.
+    pub gap_chance: f32,
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Self {
+            columns: 12,
+            rows: 8,
+            difficulty: 0.3,
+            density: 0.8,
+            density_curve: DensityCurve::Flat,
+            symmetry: Symmetry::Mirror,
+            gap_chance: 0.1,
+        }
+    }
+
+    /// Settings for round `round` of endless mode, starting from 0. Each round is a little
+    /// harder than the last, and the layout settings are picked by the round's seed.
+    pub fn endless(round: u32, seed: u64) -> Self {
+        // Mixing the seed before the round goes in keeps nearby seeds and rounds from landing on
+        // the same settings.
+        let mut rng = XorShiftRng::seed_from_u64(split_mix(split_mix(seed) ^ u64::from(round)));
+        let curves = [
+            DensityCurve::Flat,
+            DensityCurve::Top,
+            DensityCurve::Bottom,
+            DensityCurve::Middle,
+        ];
+        let symmetries = [Symmetry::None, Symmetry::Mirror, Symmetry::Quad];
+
+        Self {
+            columns: 2 * rng.gen_range(4, 8),
+            rows: rng.gen_range(5, 11),
+            difficulty: 1. - 0.85_f32.powi(round as i32),
+            density: rng.gen_range(0.6, 0.95),
+            density_curve: curves[rng.gen_range(0, curves.len())],
+            symmetry: symmetries[rng.gen_range(0, symmetries.len())],
+            gap_chance: rng.gen_range(0., 0.2),
+        }
+    }
+
+    /// A seed that is the same for everyone on a given day, counting days since the Unix epoch.
+    pub fn daily_seed(day: u64) -> u64 {
+        split_mix(day)
+    }
+
+    /// Makes the level for `seed`. The builder still needs a `dt`.
+    pub fn generate(&self, seed: u64) -> BreakoutBuilder {
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+        let difficulty = self.difficulty.clamp(0., 1.);
+        let columns = self.columns.max(1) as usize;
+        let rows = self.rows.clamp(1, MAX_ROWS) as usize;
+
+        // Only the cells that aren't mirrored from somewhere else are rolled for.
+        let free_columns = match self.symmetry {
+            Symmetry::None => columns,
+            Symmetry::Mirror | Symmetry::Quad => columns.div_ceil(2),
+        };
+        let free_rows = match self.symmetry {
+            Symmetry::None | Symmetry::Mirror => rows,
+            Symmetry::Quad => rows.div_ceil(2),
+        };
+
+        let gaps: Vec<bool> = (0..free_columns)
+            .map(|_| rng.gen::<f32>() < self.gap_chance)
+            .collect();
+
+        let mut cells = vec![vec![None; columns]; rows];
+        for (row, cells) in cells.iter_mut().enumerate().take(free_rows) {
+            let height = if rows > 1 {
+                row as f32 / (rows - 1) as f32
+            } else {
+                0.
+            };
+            let chance = self.density * self.density_curve.weight(height);
+
+            for (cell, &gap) in cells.iter_mut().zip(&gaps) {
+                let filled = rng.gen::<f32>() < chance;
+                let kind = block_kind(&mut rng, difficulty);
+                if filled && !gap {
+                    *cell = Some(kind);
+                }
+            }
+        }
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let (from_row, from_column) = match self.symmetry {
+                    Symmetry::None => (row, column),
+                    Symmetry::Mirror => (row, column.min(columns - 1 - column)),
+                    Symmetry::Quad => (row.min(rows - 1 - row), column.min(columns - 1 - column)),
+                };
+                cells[row][column] = cells[from_row][from_column];
+            }
+        }
+
+        // A level with nothing to destroy is cleared as soon as it is built, before the ball is
+        // even launched, so make sure there is something to break.
+        let destructible = |kind: &Option<(BlockKind, u32)>| match *kind {
+            Some((BlockKind::Steel, _)) | None => false,
+            Some(_) => true,
+        };
+        if !cells.iter().flatten().any(destructible) {
+            let row = rows / 2;
+            cells[row][columns / 2] = Some((BlockKind::Normal, 1));
+            cells[row][(columns - 1) / 2] = Some((BlockKind::Normal, 1));
+        }
+
+        let width = (GRID_RIGHT - GRID_LEFT) / columns as f32;
+        let mut builder = self.base(difficulty).seed(seed);
+        let mut blocks = 0;
+        for (row, cells) in cells.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if let Some((kind, hit_points)) = *cell {
+                    let origin = [
+                        GRID_LEFT + column as f32 * width,
+                        GRID_TOP - (row + 1) as f32 * ROW_HEIGHT,
+                    ];
+                    builder = builder.add_block(
+                        Block::new([width, ROW_HEIGHT], origin)
+                            .with_kind(kind)
+                            .with_hit_points(hit_points),
+                    );
+                    if destructible(cell) {
+                        blocks += 1;
+                    }
+                }
+            }
+        }
+
+        builder.par_time(blocks as f32 * PAR_TIME_PER_BLOCK)
+    }
+
+    /// Everything but the blocks.
+    fn base(&self, difficulty: f32) -> BreakoutBuilder {
+        let ball_speed = 0.5 + 0.3 * difficulty;
+        let paddle_width = 0.18 - 0.06 * difficulty;
+
+        BreakoutBuilder::new()
+            .ball(Ball::new(0.015, [0.5, 0.3], [0., -ball_speed]))
+            .paddle(Paddle::new(
+                [paddle_width, 0.02],
+                [0.5 - paddle_width / 2., 0.065],
+            ))
+            .serve()
+            .drop_table(
+                DropTable::new(0.3 - 0.15 * difficulty)
+                    .with(PowerUp::WidePaddle, 3)
+                    .with(PowerUp::SlowBall, 3)
+                    .with(PowerUp::BigBall, 2)
+                    .with(PowerUp::SmallBall, 1)
+                    .with(PowerUp::MultiBall, 2)
+                    .with(PowerUp::Laser, 2)
+                    .with(PowerUp::Catch, 2)
+                    .with(PowerUp::ExtraLife, 1),
+            )
+    }
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Picks a block, with tougher ones more likely the harder the level is.
+fn block_kind<R: Rng>(rng: &mut R, difficulty: f32) -> (BlockKind, u32) {
+    let roll = rng.gen::<f32>();
+    let steel = 0.08 * difficulty;
+    let explosive = steel + 0.06;
+    let armored = explosive + 0.25 * difficulty;
+
+    if roll < steel {
+        (BlockKind::Steel, 1)
+    } else if roll < explosive {
+        (BlockKind::Explosive { radius: 0.12 }, 1)
+    } else if roll < armored {
+        let min_speed = 0.6 + 0.3 * difficulty;
+        (
+            BlockKind::Armored { min_speed },
+            2 + (2. * difficulty) as u32,
+        )
+    } else {
+        (
+            BlockKind::Normal,
+            1 + (rng.gen::<f32>() < difficulty) as u32,
+        )
+    }
+}
+
+/// SplitMix64, which scrambles `z` so that values close together come out unrelated.
+fn split_mix(z: u64) -> u64 {
+    let mut z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
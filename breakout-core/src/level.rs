@@ -0,0 +1,538 @@
+//! A plain text format for levels.
+//!
+//! A level file is a header of `key: value` lines, followed by a grid of blocks drawn with
+//! characters:
+//!
+//! ```text
+//! // Lines starting with // are comments.
+//! name: First Steps
+//! author: Andrew Gaspar
+//! par: 60
+//! ball: 0.015 0.5 0.7 0 -0.5
+//! paddle: 0.15 0.02 0.425 0.065
+//! serve: true
+//! physics: paddle_speed=0.8 max_ball_speed=1.5
+//! drops: 0.25 wide_paddle=3 extra_life=1
+//! cell: 0.1 0.05
+//! origin: 0 0.95
+//! legend: A armored 2 1.5
+//! grid:
+//! ##########
+//! #@@AAAA@@#
+//! ```
+//!
+//! The ball is given as its radius, center and velocity, and the paddle as its size and bottom
+//! left corner. Each character in the grid is one `cell` sized block, with the top left of the
+//! grid at `origin`. `#` is a normal block and `@` is a steel block unless the legend says
+//! otherwise, and anything not in the legend is empty space. Block kinds are written as
+//! `normal [hit points]`, `steel`, `armored <hit points> <min speed>` or
+//! `explosive <hit points> <radius>`. Blocks that don't fit the grid can be placed one at a time
+//! with `block: <width> <height> <left> <bottom> <kind>`.
+
+use prelude::*;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The size of a grid cell when a level doesn't say.
+const DEFAULT_CELL: vec2 = [0.1, 0.05];
+
+/// Where the top left of the grid is when a level doesn't say.
+const DEFAULT_ORIGIN: vec2 = [0., 1.];
+
+/// Characters the serializer gives to block kinds not in the default legend, in order.
+const LEGEND_CHARS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// How far off of the grid a block can be and still be written as part of it.
+const GRID_TOLERANCE: f32 = 1e-4;
+
+/// The name and author of a level. They don't change how it plays.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LevelInfo {
+    pub name: String,
+    pub author: String,
+}
+
+/// A level read from, or to be written to, the text format.
+#[derive(Debug, Clone)]
+pub struct Level {
+    info: LevelInfo,
+    builder: BreakoutBuilder,
+}
+
+/// What went wrong reading a level, and on which line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelError {
+    line: usize,
+    message: String,
+}
+
+impl LevelError {
+    pub(crate) fn new<S: Into<String>>(line: usize, message: S) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// The line the error is on, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for LevelError {}
+
+impl Level {
+    /// Reads a level. The builder it makes still needs a `dt` before the game can be built.
+    pub fn parse(text: &str) -> Result<Self, LevelError> {
+        let mut info = LevelInfo::default();
+        let mut builder = BreakoutBuilder::new();
+        let mut cell = DEFAULT_CELL;
+        let mut origin = DEFAULT_ORIGIN;
+        let mut legend = default_legend();
+
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        for (number, line) in &mut lines {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let (key, value) = match line.find(':') {
+                Some(colon) => (line[..colon].trim(), line[colon + 1..].trim()),
+                None => return Err(LevelError::new(number, "expected `key: value`")),
+            };
+
+            let mut words = Words::new(number, value);
+            match key {
+                "name" => {
+                    info.name = value.to_owned();
+                    continue;
+                }
+                "author" => {
+                    info.author = value.to_owned();
+                    continue;
+                }
+                "par" => builder = builder.par_time(words.next()?),
+                "lives" => builder = builder.lives(words.next()?),
+                "seed" => builder = builder.seed(words.next()?),
+                "serve" => builder.serve = words.next()?,
+                "sticky" => builder.sticky_paddle = words.next()?,
+                "ball" => {
+                    let radius = words.next()?;
+                    let center = [words.next()?, words.next()?];
+                    let velocity = [words.next()?, words.next()?];
+                    builder = builder.ball(Ball::new(radius, center, velocity));
+                }
+                "paddle" => {
+                    let dimensions = [words.next()?, words.next()?];
+                    let origin = [words.next()?, words.next()?];
+                    builder = builder.paddle(Paddle::new(dimensions, origin));
+                }
+                "physics" => builder.physics = parse_physics(builder.physics, &mut words)?,
+                "drops" => builder = builder.drop_table(parse_drops(&mut words)?),
+                "cell" => cell = [words.next()?, words.next()?],
+                "origin" => origin = [words.next()?, words.next()?],
+                "legend" => {
+                    let symbol = words.next_char()?;
+                    let kind = parse_kind(&mut words)?;
+                    legend.retain(|&(c, _)| c != symbol);
+                    legend.push((symbol, kind));
+                }
+                "block" => {
+                    let dimensions = [words.next()?, words.next()?];
+                    let origin = [words.next()?, words.next()?];
+                    let (kind, hit_points) = parse_kind(&mut words)?;
+                    builder = builder.add_block(
+                        Block::new(dimensions, origin)
+                            .with_kind(kind)
+                            .with_hit_points(hit_points),
+                    );
+                }
+                "grid" => break,
+                _ => return Err(LevelError::new(number, format!("unknown key `{}`", key))),
+            }
+
+            words.finish()?;
+        }
+
+        // Everything after the grid line is the grid itself.
+        let [width, height] = cell;
+        for (row, (_, line)) in lines.enumerate() {
+            for (column, symbol) in line.chars().enumerate() {
+                if let Some(&(_, (kind, hit_points))) = legend.iter().find(|&&(c, _)| c == symbol) {
+                    let left = origin[0] + column as f32 * width;
+                    let bottom = origin[1] - (row + 1) as f32 * height;
+                    builder = builder.add_block(
+                        Block::new(cell, [left, bottom])
+                            .with_kind(kind)
+                            .with_hit_points(hit_points),
+                    );
+                }
+            }
+        }
+
+        Ok(Self { info, builder })
+    }
+
+    pub fn new(info: LevelInfo, builder: BreakoutBuilder) -> Self {
+        Self { info, builder }
+    }
+
+    /// The level `game` is playing, with the blocks that are left.
+    pub fn from_game(info: LevelInfo, game: &Breakout) -> Self {
+        Self {
+            info,
+            builder: game.to_builder(),
+        }
+    }
+
+    pub fn info(&self) -> &LevelInfo {
+        &self.info
+    }
+
+    pub fn par_time(&self) -> Option<f32> {
+        self.builder.par_time
+    }
+
+    /// A builder for a new game of this level.
+    pub fn builder(&self) -> BreakoutBuilder {
+        self.builder.clone()
+    }
+}
+
+impl fmt::Display for Level {
+    /// Writes the level out in the text format.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, true)
+    }
+}
+
+impl Level {
+    /// Writes the level out, with the blocks on a grid if `grid` is set and they fit on one.
+    /// Positions worked out from a grid can be off in the last bit, so replays write every block
+    /// out exactly.
+    pub(crate) fn write(&self, f: &mut fmt::Formatter, grid: bool) -> fmt::Result {
+        let builder = &self.builder;
+
+        if !self.info.name.is_empty() {
+            writeln!(f, "name: {}", self.info.name)?;
+        }
+        if !self.info.author.is_empty() {
+            writeln!(f, "author: {}", self.info.author)?;
+        }
+        if let Some(par_time) = builder.par_time {
+            writeln!(f, "par: {}", par_time)?;
+        }
+        writeln!(f, "lives: {}", builder.lives)?;
+        writeln!(f, "seed: {}", builder.seed)?;
+        writeln!(f, "serve: {}", builder.serve)?;
+        writeln!(f, "sticky: {}", builder.sticky_paddle)?;
+
+        if let Some(ball) = builder.ball {
+            let ([x, y], [vx, vy]) = (ball.location(), ball.velocity());
+            writeln!(f, "ball: {} {} {} {} {}", ball.radius(), x, y, vx, vy)?;
+        }
+        if let Some(paddle) = builder.paddle {
+            let ([width, height], [x, y]) = (paddle.dimensions(), paddle.location());
+            writeln!(f, "paddle: {} {} {} {}", width, height, x, y)?;
+        }
+
+        let physics = &builder.physics;
+        writeln!(
+            f,
+            "physics: paddle_speed={} paddle_hit_speedup={} max_horizontal_fraction={} \
+             max_ball_speed={} min_vertical_speed={} wall_restitution={}",
+            physics.paddle_speed,
+            physics.paddle_hit_speedup,
+            physics.max_horizontal_fraction,
+            physics.max_ball_speed,
+            physics.min_vertical_speed,
+            physics.wall_restitution
+        )?;
+
+        let drops = &builder.drop_table;
+        if !drops.weights().is_empty() {
+            write!(f, "drops: {}", drops.chance())?;
+            for &(power_up, weight) in drops.weights() {
+                write!(f, " {}={}", power_up_name(power_up), weight)?;
+            }
+            writeln!(f)?;
+        }
+
+        let blocks: Vec<Block> = builder.blocks.iter().flatten().cloned().collect();
+        match Grid::fit(&blocks).filter(|_| grid) {
+            Some(grid) => grid.write(f),
+            None => {
+                for block in &blocks {
+                    let [width, height] = block.dimensions();
+                    write!(
+                        f,
+                        "block: {} {} {} {} ",
+                        width,
+                        height,
+                        block.left(),
+                        block.bottom()
+                    )?;
+                    write_kind(f, block.kind(), block.max_hit_points())?;
+                    writeln!(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The blocks of a level laid out on a grid, for writing out.
+struct Grid {
+    cell: vec2,
+    origin: vec2,
+    legend: Vec<(char, (BlockKind, u32))>,
+    rows: Vec<Vec<char>>,
+}
+
+impl Grid {
+    /// Lays `blocks` out on a grid, if they are all the same size and line up with each other.
+    fn fit(blocks: &[Block]) -> Option<Self> {
+        let cell = blocks.first()?.dimensions();
+        let [width, height] = cell;
+        let left = blocks
+            .iter()
+            .map(|b| b.left())
+            .fold(f32::INFINITY, f32::min);
+        let top = blocks
+            .iter()
+            .map(|b| b.top())
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        let defaults = default_legend();
+        let mut legend: Vec<(char, (BlockKind, u32))> = vec![];
+        let mut rows: Vec<Vec<char>> = vec![];
+
+        for block in blocks {
+            if block.dimensions() != cell {
+                return None;
+            }
+
+            let column = (block.left() - left) / width;
+            let row = (top - block.top()) / height;
+            if (column - column.round()).abs() * width > GRID_TOLERANCE
+                || (row - row.round()).abs() * height > GRID_TOLERANCE
+            {
+                return None;
+            }
+            let (column, row) = (column.round() as usize, row.round() as usize);
+
+            let kind = (block.kind(), block.max_hit_points());
+            let symbol = match legend.iter().chain(&defaults).find(|&&(_, k)| k == kind) {
+                Some(&(symbol, _)) => symbol,
+                None => {
+                    let symbol = LEGEND_CHARS.chars().nth(legend.len())?;
+                    legend.push((symbol, kind));
+                    symbol
+                }
+            };
+
+            if rows.len() <= row {
+                rows.resize(row + 1, vec![]);
+            }
+            let cells = &mut rows[row];
+            if cells.len() <= column {
+                cells.resize(column + 1, '.');
+            }
+            cells[column] = symbol;
+        }
+
+        Some(Self {
+            cell,
+            origin: [left, top],
+            legend,
+            rows,
+        })
+    }
+
+    fn write(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "cell: {} {}", self.cell[0], self.cell[1])?;
+        writeln!(f, "origin: {} {}", self.origin[0], self.origin[1])?;
+        for &(symbol, (kind, hit_points)) in &self.legend {
+            write!(f, "legend: {} ", symbol)?;
+            write_kind(f, kind, hit_points)?;
+            writeln!(f)?;
+        }
+
+        writeln!(f, "grid:")?;
+        for row in &self.rows {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// The whitespace separated values on a line.
+pub(crate) struct Words<'a> {
+    line: usize,
+    words: ::std::str::SplitWhitespace<'a>,
+}
+
+impl<'a> Words<'a> {
+    pub(crate) fn new(line: usize, text: &'a str) -> Self {
+        Self {
+            line,
+            words: text.split_whitespace(),
+        }
+    }
+
+    pub(crate) fn error<S: Into<String>>(&self, message: S) -> LevelError {
+        LevelError::new(self.line, message)
+    }
+
+    fn next_word(&mut self) -> Result<&'a str, LevelError> {
+        match self.words.next() {
+            Some(word) => Ok(word),
+            None => Err(self.error("line ended early")),
+        }
+    }
+
+    /// The next word, if the line has any left.
+    pub(crate) fn try_next_word(&mut self) -> Option<&'a str> {
+        self.words.next()
+    }
+
+    pub(crate) fn next<T: FromStr>(&mut self) -> Result<T, LevelError> {
+        let word = self.next_word()?;
+        word.parse()
+            .map_err(|_| self.error(format!("`{}` is not a valid value here", word)))
+    }
+
+    fn next_char(&mut self) -> Result<char, LevelError> {
+        let word = self.next_word()?;
+        let mut chars = word.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.error(format!("`{}` should be a single character", word))),
+        }
+    }
+
+    /// Splits the next word into the parts either side of an `=`.
+    fn next_pair(&mut self) -> Result<Option<(&'a str, &'a str)>, LevelError> {
+        let word = match self.words.next() {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+
+        match word.find('=') {
+            Some(equals) => Ok(Some((&word[..equals], &word[equals + 1..]))),
+            None => Err(self.error(format!("expected `name=value`, not `{}`", word))),
+        }
+    }
+
+    fn parse<T: FromStr>(&self, word: &str) -> Result<T, LevelError> {
+        word.parse()
+            .map_err(|_| self.error(format!("`{}` is not a valid value here", word)))
+    }
+
+    /// Makes sure nothing was left over on the line.
+    pub(crate) fn finish(mut self) -> Result<(), LevelError> {
+        match self.words.next() {
+            Some(word) => Err(self.error(format!("unexpected `{}`", word))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_kind(words: &mut Words) -> Result<(BlockKind, u32), LevelError> {
+    match words.next_word()? {
+        "normal" => {
+            let hit_points = match words.words.clone().next() {
+                Some(_) => words.next()?,
+                None => 1,
+            };
+            Ok((BlockKind::Normal, hit_points))
+        }
+        "steel" => Ok((BlockKind::Steel, 1)),
+        "armored" => {
+            let hit_points = words.next()?;
+            let min_speed = words.next()?;
+            Ok((BlockKind::Armored { min_speed }, hit_points))
+        }
+        "explosive" => {
+            let hit_points = words.next()?;
+            let radius = words.next()?;
+            Ok((BlockKind::Explosive { radius }, hit_points))
+        }
+        kind => Err(words.error(format!("unknown block kind `{}`", kind))),
+    }
+}
+
+fn write_kind(f: &mut fmt::Formatter, kind: BlockKind, hit_points: u32) -> fmt::Result {
+    match kind {
+        BlockKind::Normal => write!(f, "normal {}", hit_points),
+        BlockKind::Steel => write!(f, "steel"),
+        BlockKind::Armored { min_speed } => write!(f, "armored {} {}", hit_points, min_speed),
+        BlockKind::Explosive { radius } => write!(f, "explosive {} {}", hit_points, radius),
+    }
+}
+
+fn parse_physics(
+    mut physics: PhysicsConfig,
+    words: &mut Words,
+) -> Result<PhysicsConfig, LevelError> {
+    while let Some((name, value)) = words.next_pair()? {
+        let value = words.parse(value)?;
+        match name {
+            "paddle_speed" => physics.paddle_speed = value,
+            "paddle_hit_speedup" => physics.paddle_hit_speedup = value,
+            "max_horizontal_fraction" => physics.max_horizontal_fraction = value,
+            "max_ball_speed" => physics.max_ball_speed = value,
+            "min_vertical_speed" => physics.min_vertical_speed = value,
+            "wall_restitution" => physics.wall_restitution = value,
+            _ => return Err(words.error(format!("unknown physics setting `{}`", name))),
+        }
+    }
+
+    Ok(physics)
+}
+
+fn parse_drops(words: &mut Words) -> Result<DropTable, LevelError> {
+    let mut drops = DropTable::new(words.next()?);
+
+    while let Some((name, weight)) = words.next_pair()? {
+        let power_up = PowerUp::ALL
+            .iter()
+            .cloned()
+            .find(|&power_up| power_up_name(power_up) == name)
+            .ok_or_else(|| words.error(format!("unknown power-up `{}`", name)))?;
+        drops = drops.with(power_up, words.parse(weight)?);
+    }
+
+    Ok(drops)
+}
+
+fn default_legend() -> Vec<(char, (BlockKind, u32))> {
+    vec![('#', (BlockKind::Normal, 1)), ('@', (BlockKind::Steel, 1))]
+}
+
+fn power_up_name(power_up: PowerUp) -> &'static str {
+    match power_up {
+        PowerUp::WidePaddle => "wide_paddle",
+        PowerUp::SlowBall => "slow_ball",
+        PowerUp::ExtraLife => "extra_life",
+        PowerUp::BigBall => "big_ball",
+        PowerUp::SmallBall => "small_ball",
+        PowerUp::MultiBall => "multi_ball",
+        PowerUp::Laser => "laser",
+        PowerUp::Catch => "catch",
+    }
+}
@@ -2,26 +2,61 @@ extern crate rand;
 
 mod ball;
 mod block;
+mod campaign;
+pub mod colors;
+mod command;
+mod controller;
+mod environment;
+mod error;
+mod event;
+mod frame;
 mod game;
+mod generator;
+mod level;
 mod math;
 mod object;
 mod paddle;
+mod phase;
+mod physics;
+mod powerup;
+mod projectile;
+mod replay;
+mod score;
 mod shape;
 
 #[cfg(test)]
 mod tests;
 
 pub use ball::Ball;
-pub use block::Block;
+pub use block::{Block, BlockKind};
+pub use campaign::{Campaign, Progress};
+pub use command::Command;
+pub use controller::{PaddleController, PredictiveController, TrackingController};
+pub use environment::{Action, Environment, Step, StepInfo};
+pub use error::{ActionError, BuildError, CampaignError};
+pub use event::{GameEvent, Wall};
+pub use frame::{Frame, PixelFormat};
 pub use game::{Breakout, BreakoutBuilder};
+pub use generator::{DensityCurve, Generator, Symmetry};
+pub use level::{Level, LevelError, LevelInfo};
 pub use math::{vec2, Vec2};
 pub use object::GameObject;
 pub use paddle::Paddle;
-pub use shape::{Circle, Rectangle, Shape};
+pub use phase::Phase;
+pub use physics::PhysicsConfig;
+pub use powerup::{Capsule, DropTable, Effect, PowerUp};
+pub use projectile::Projectile;
+pub use replay::Replay;
+pub use score::Score;
+pub use shape::{Circle, Face, Rectangle, Shape};
 
 pub mod prelude {
     pub use super::{
-        vec2, Ball, Block, Breakout, BreakoutBuilder, Circle, GameObject, Paddle, Rectangle, Shape,
-        Vec2,
+        vec2, Action, ActionError, Ball, Block, BlockKind, Breakout, BreakoutBuilder, BuildError,
+        Campaign, CampaignError, Capsule, Circle, Command, DensityCurve, DropTable, Effect,
+        Environment, Face, Frame, GameEvent, GameObject, Generator, Level, LevelError, LevelInfo,
+        Paddle, PaddleController, Phase, PhysicsConfig, PixelFormat, PowerUp, PredictiveController,
+        Progress, Projectile, Rectangle, Replay, Score, Shape, Step, StepInfo, Symmetry,
+        TrackingController, Vec2, Wall,
     };
 }
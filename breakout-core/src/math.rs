@@ -19,6 +19,40 @@ impl Vec2 for vec2 {
     }
 }
 
+/// How far apart, in units, two things can be and still count as touching.
+pub const EPSILON: f32 = 1e-5;
+
+pub fn add(a: vec2, b: vec2) -> vec2 {
+    [a.x() + b.x(), a.y() + b.y()]
+}
+
+pub fn sub(a: vec2, b: vec2) -> vec2 {
+    [a.x() - b.x(), a.y() - b.y()]
+}
+
+pub fn scale(a: vec2, s: f32) -> vec2 {
+    [a.x() * s, a.y() * s]
+}
+
+pub fn dot(a: vec2, b: vec2) -> f32 {
+    a.x() * b.x() + a.y() * b.y()
+}
+
+pub fn length(a: vec2) -> f32 {
+    dot(a, a).sqrt()
+}
+
+/// Reflects `v` off of a surface with the unit normal `n`.
+pub fn reflect(v: vec2, n: vec2) -> vec2 {
+    sub(v, scale(n, 2. * dot(v, n)))
+}
+
+/// Rotates `v` counter-clockwise by `angle` radians.
+pub fn rotate(v: vec2, angle: f32) -> vec2 {
+    let (sin, cos) = angle.sin_cos();
+    [v[0] * cos - v[1] * sin, v[0] * sin + v[1] * cos]
+}
+
 pub fn next_point(start: vec2, velocity: vec2, dt: f32) -> vec2 {
     [start.x() + velocity.x() * dt, start.y() + velocity.y() * dt]
 }
@@ -38,10 +72,10 @@ pub fn overlapping_segments(a: vec2, b: vec2) -> bool {
 
 #[test]
 fn basic_segments() {
-    assert!(overlapping_segments((1., 3.), (2., 4.)));
-    assert!(overlapping_segments((1., 3.), (3., 5.)));
-    assert!(!overlapping_segments((1., 3.), (4., 5.)));
-    assert!(overlapping_segments((1., 5.), (2., 4.)));
+    assert!(overlapping_segments([1., 3.], [2., 4.]));
+    assert!(overlapping_segments([1., 3.], [3., 5.]));
+    assert!(!overlapping_segments([1., 3.], [4., 5.]));
+    assert!(overlapping_segments([1., 5.], [2., 4.]));
 }
 
 pub fn overlapping_boxes(a: &shape::Box, b: &shape::Box) -> bool {
@@ -52,11 +86,287 @@ pub fn overlapping_boxes(a: &shape::Box, b: &shape::Box) -> bool {
 #[test]
 pub fn basic_boxes() {
     assert!(overlapping_boxes(
-        ((1., 3.), (3., 1.)),
-        ((2., 4.), (4., 2.))
+        &shape::Box {
+            left: 1.,
+            right: 3.,
+            bottom: 1.,
+            top: 3.,
+        },
+        &shape::Box {
+            left: 2.,
+            right: 4.,
+            bottom: 2.,
+            top: 4.,
+        }
     ));
 }
 
-pub fn objects_are_close<A: Shape, B: Shape>(a: &A, b: &B) -> bool {
-    overlapping_boxes(&a.bounding_box(), &b.bounding_box())
+/// The bounding box of everything a circle touches while it moves by `displacement`.
+pub fn swept_bounding_box(center: vec2, radius: f32, displacement: vec2) -> shape::Box {
+    let end = add(center, displacement);
+
+    shape::Box {
+        left: center.x().min(end.x()) - radius,
+        right: center.x().max(end.x()) + radius,
+        bottom: center.y().min(end.y()) - radius,
+        top: center.y().max(end.y()) + radius,
+    }
+}
+
+/// Whether the insides of two boxes overlap. Boxes that only share an edge don't count.
+pub fn intersecting_boxes(a: &shape::Box, b: &shape::Box) -> bool {
+    a.left < b.right - EPSILON
+        && b.left < a.right - EPSILON
+        && a.bottom < b.top - EPSILON
+        && b.bottom < a.top - EPSILON
+}
+
+/// Whether `b` lies entirely inside of the unit square the game is played in.
+pub fn inside_arena(b: &shape::Box) -> bool {
+    b.left >= -EPSILON && b.right <= 1. + EPSILON && b.bottom >= -EPSILON && b.top <= 1. + EPSILON
+}
+
+/// The point inside of `b` that is closest to `point`.
+pub fn closest_point_on_box(point: vec2, b: &shape::Box) -> vec2 {
+    [
+        point.x().max(b.left).min(b.right),
+        point.y().max(b.bottom).min(b.top),
+    ]
+}
+
+/// If a circle overlaps the box `b`, returns the unit normal to push it out along and how far it
+/// has to be pushed.
+pub fn circle_box_penetration(center: vec2, radius: f32, b: &shape::Box) -> Option<(vec2, f32)> {
+    let offset = sub(center, closest_point_on_box(center, b));
+    let distance = length(offset);
+
+    if distance >= radius {
+        None
+    } else if distance > 0. {
+        Some((scale(offset, 1. / distance), radius - distance))
+    } else {
+        // The center is inside the box, so it has to come out the nearest side.
+        let normal = escape_normal(center, b);
+        let depth = dot(
+            sub(center, closest_edge_point(center, b, normal)),
+            scale(normal, -1.),
+        );
+        Some((normal, radius + depth))
+    }
+}
+
+/// Projects a point inside of `b` onto the side of it facing `normal`.
+fn closest_edge_point(point: vec2, b: &shape::Box, normal: vec2) -> vec2 {
+    match (normal.x(), normal.y()) {
+        (x, _) if x < 0. => [b.left, point.y()],
+        (x, _) if x > 0. => [b.right, point.y()],
+        (_, y) if y < 0. => [point.x(), b.bottom],
+        _ => [point.x(), b.top],
+    }
+}
+
+/// Describes the first moment a moving circle touches something.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Contact {
+    /// How far along its displacement the circle travelled before touching, from 0 to 1.
+    pub time: f32,
+
+    /// Unit normal of the touched surface, pointing back towards the circle.
+    pub normal: vec2,
+}
+
+/// Returns the range of times during which a point moving along one axis is between `min` and
+/// `max`, or None if it never is.
+fn slab(start: f32, displacement: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    if displacement == 0. {
+        if start < min || start > max {
+            None
+        } else {
+            Some((f32::NEG_INFINITY, f32::INFINITY))
+        }
+    } else {
+        let t1 = (min - start) / displacement;
+        let t2 = (max - start) / displacement;
+        Some((t1.min(t2), t1.max(t2)))
+    }
+}
+
+/// Finds when a circle moving by `displacement` first touches `point`.
+pub fn sweep_circle_point(
+    center: vec2,
+    radius: f32,
+    displacement: vec2,
+    point: vec2,
+) -> Option<Contact> {
+    let m = sub(center, point);
+    let a = dot(displacement, displacement);
+    let b = 2. * dot(m, displacement);
+    let c = dot(m, m) - radius * radius;
+
+    if a == 0. {
+        return None;
+    }
+
+    let discriminant = b * b - 4. * a * c;
+    if discriminant < 0. {
+        return None;
+    }
+
+    // We only care about the first root - the second is when the circle leaves the point.
+    let time = (-b - discriminant.sqrt()) / (2. * a);
+    if !(0. ..=1.).contains(&time) {
+        return None;
+    }
+
+    let offset = add(m, scale(displacement, time));
+    Some(Contact {
+        time,
+        normal: scale(offset, 1. / length(offset)),
+    })
+}
+
+/// Picks the side of `b` that a point inside of it is closest to escaping through.
+fn escape_normal(point: vec2, b: &shape::Box) -> vec2 {
+    let candidates = [
+        (point.x() - b.left, [-1., 0.]),
+        (b.right - point.x(), [1., 0.]),
+        (point.y() - b.bottom, [0., -1.]),
+        (b.top - point.y(), [0., 1.]),
+    ];
+
+    let mut best = candidates[0];
+    for &candidate in &candidates[1..] {
+        if candidate.0 < best.0 {
+            best = candidate;
+        }
+    }
+    best.1
+}
+
+/// Finds when a circle moving by `displacement` first touches the box `b`. The box is treated as
+/// being rounded out by the circle's radius, so contacts can happen on a face or a corner.
+///
+/// A circle that already overlaps the box reports a contact at time 0, but only if it is still
+/// moving further in. This keeps a ball that has just bounced from immediately bouncing again.
+pub fn sweep_circle_box(
+    center: vec2,
+    radius: f32,
+    displacement: vec2,
+    b: &shape::Box,
+) -> Option<Contact> {
+    if let Some((normal, _)) = circle_box_penetration(center, radius, b) {
+        return if dot(displacement, normal) < 0. {
+            Some(Contact { time: 0., normal })
+        } else {
+            None
+        };
+    }
+
+    let (tx_enter, tx_exit) = slab(
+        center.x(),
+        displacement.x(),
+        b.left - radius,
+        b.right + radius,
+    )?;
+    let (ty_enter, ty_exit) = slab(
+        center.y(),
+        displacement.y(),
+        b.bottom - radius,
+        b.top + radius,
+    )?;
+
+    let enter = tx_enter.max(ty_enter);
+    let exit = tx_exit.min(ty_exit);
+    if enter > exit || enter > 1. || exit < 0. {
+        return None;
+    }
+
+    let time = enter.max(0.);
+    let hit = add(center, scale(displacement, time));
+    let within_x = hit.x() >= b.left && hit.x() <= b.right;
+    let within_y = hit.y() >= b.bottom && hit.y() <= b.top;
+
+    if within_x || within_y {
+        // Starting inside the rounded out box without overlapping the box itself only happens
+        // when the circle is resting right on a face and rounding puts it a hair inside.
+        if enter < 0. {
+            return None;
+        }
+
+        let normal = if tx_enter > ty_enter {
+            [-displacement.x().signum(), 0.]
+        } else {
+            [0., -displacement.y().signum()]
+        };
+
+        if dot(displacement, normal) >= 0. {
+            return None;
+        }
+
+        Some(Contact { time, normal })
+    } else {
+        // The rounded out box's corners are circles around the corners of the box.
+        let corner = [
+            if hit.x() < b.left { b.left } else { b.right },
+            if hit.y() < b.bottom { b.bottom } else { b.top },
+        ];
+        sweep_circle_point(center, radius, displacement, corner)
+    }
+}
+
+#[cfg(test)]
+fn unit_box() -> shape::Box {
+    shape::Box {
+        left: 0.,
+        right: 1.,
+        bottom: 0.,
+        top: 1.,
+    }
+}
+
+#[test]
+fn sweep_hits_face() {
+    // Falls onto the top face from well above - the contact happens once the bottom of the
+    // circle reaches y = 1.
+    let contact = sweep_circle_box([0.5, 2.], 0.5, [0., -2.], &unit_box()).unwrap();
+    assert!((contact.time - 0.25).abs() < 1e-6);
+    assert_eq!(contact.normal, [0., 1.]);
+}
+
+#[test]
+fn sweep_does_not_tunnel() {
+    // Moves far enough in one step to completely pass the box.
+    let contact = sweep_circle_box([-5., 0.5], 0.1, [10., 0.], &unit_box()).unwrap();
+    assert!((contact.time - 0.49).abs() < 1e-6);
+    assert_eq!(contact.normal, [-1., 0.]);
+}
+
+#[test]
+fn sweep_hits_corner() {
+    // Moves diagonally towards the top right corner.
+    let contact = sweep_circle_box([2., 2.], 0.5, [-2., -2.], &unit_box()).unwrap();
+    let expected = 0.5_f32.sqrt();
+    assert!((contact.normal[0] - expected).abs() < 1e-5);
+    assert!((contact.normal[1] - expected).abs() < 1e-5);
+}
+
+#[test]
+fn sweep_misses() {
+    assert!(sweep_circle_box([2., 2.], 0.1, [0., -4.], &unit_box()).is_none());
+    assert!(sweep_circle_box([0.5, 2.], 0.1, [0., 0.5], &unit_box()).is_none());
+    assert!(sweep_circle_box([0.5, 2.], 0.1, [0., -0.5], &unit_box()).is_none());
+}
+
+#[test]
+fn sweep_lets_resting_circle_leave() {
+    // Sits on the paddle where a served ball is put, which rounds to a hair inside of the
+    // rounded out box, and moves up and away.
+    let paddle = shape::Box {
+        left: 0.425,
+        right: 0.575,
+        bottom: 0.065,
+        top: 0.085,
+    };
+    let center = [0.5, paddle.top + 0.015];
+    assert!(sweep_circle_box(center, 0.015, [0., 0.0005], &paddle).is_none());
 }
@@ -20,6 +20,10 @@ impl Paddle {
     pub fn dimensions(&self) -> vec2 {
         self.dimensions
     }
+
+    pub fn set_dimensions(&mut self, dimensions: vec2) {
+        self.dimensions = dimensions
+    }
 }
 
 impl Shape for Paddle {
@@ -0,0 +1,18 @@
+/// The state the game is in, which decides what ticking it does.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Phase {
+    /// A new ball is waiting to be put into play. Only the paddle moves.
+    Serve,
+
+    /// The ball is in play.
+    Playing,
+
+    /// Nothing moves until the game is resumed.
+    Paused,
+
+    /// Every block has been destroyed. Ticking the game has no further effect.
+    LevelCleared,
+
+    /// The last ball was lost. Ticking the game has no further effect.
+    GameOver,
+}
@@ -0,0 +1,44 @@
+/// The numbers that decide how the ball and paddle move, so levels and game modes can be tuned
+/// without touching the simulation itself.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PhysicsConfig {
+    /// How fast the paddle moves when it is steered all the way to one side, in units per
+    /// second.
+    pub paddle_speed: f32,
+
+    /// How much faster the ball gets every time it bounces off of the top of the paddle.
+    pub paddle_hit_speedup: f32,
+
+    /// The largest fraction of the ball's speed that can go sideways when it bounces off of the
+    /// end of the paddle.
+    pub max_horizontal_fraction: f32,
+
+    /// The fastest the ball can go after a bounce, in units per second.
+    pub max_ball_speed: f32,
+
+    /// The slowest the ball can move up or down after a bounce, in units per second. Keeps the
+    /// ball from getting stuck going back and forth across the arena.
+    pub min_vertical_speed: f32,
+
+    /// How much of the ball's speed into a wall it keeps when it bounces off, from 0 to 1.
+    pub wall_restitution: f32,
+}
+
+impl PhysicsConfig {
+    pub fn new() -> Self {
+        Self {
+            paddle_speed: 0.7,
+            paddle_hit_speedup: 1.05,
+            max_horizontal_fraction: 0.8,
+            max_ball_speed: 2.,
+            min_vertical_speed: 0.1,
+            wall_restitution: 1.,
+        }
+    }
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,245 @@
+use prelude::*;
+use rand::Rng;
+use shape::Box;
+
+/// How fast capsules fall, in units per second.
+const CAPSULE_SPEED: f32 = 0.25;
+
+/// The size of every capsule.
+const CAPSULE_DIMENSIONS: vec2 = [0.06, 0.025];
+
+/// Effects the paddle can pick up by catching a capsule.
+///
+/// Catching a timed power-up that is already active restarts its timer rather than making it
+/// any stronger. The big and small ball power-ups cancel each other out, so catching one ends
+/// the other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PowerUp {
+    /// Makes the paddle half again as wide.
+    WidePaddle,
+
+    /// Slows the ball down.
+    SlowBall,
+
+    /// Gives the player another life.
+    ExtraLife,
+
+    /// Makes the ball half again as big.
+    BigBall,
+
+    /// Shrinks the ball.
+    SmallBall,
+
+    /// Splits every ball in play into three.
+    MultiBall,
+
+    /// Lets the paddle fire lasers.
+    Laser,
+
+    /// Makes the paddle catch the ball, so it can be aimed before it is launched again.
+    Catch,
+}
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 8] = [
+        PowerUp::WidePaddle,
+        PowerUp::SlowBall,
+        PowerUp::ExtraLife,
+        PowerUp::BigBall,
+        PowerUp::SmallBall,
+        PowerUp::MultiBall,
+        PowerUp::Laser,
+        PowerUp::Catch,
+    ];
+
+    /// How many seconds the power-up lasts, or None if it takes effect once and is done.
+    pub fn duration(&self) -> Option<f32> {
+        match *self {
+            PowerUp::WidePaddle | PowerUp::Catch => Some(15.),
+            PowerUp::SlowBall | PowerUp::BigBall | PowerUp::SmallBall | PowerUp::Laser => Some(10.),
+            PowerUp::ExtraLife | PowerUp::MultiBall => None,
+        }
+    }
+
+    /// The power-up that catching this one cancels, if any.
+    pub fn cancels(&self) -> Option<PowerUp> {
+        match *self {
+            PowerUp::BigBall => Some(PowerUp::SmallBall),
+            PowerUp::SmallBall => Some(PowerUp::BigBall),
+            PowerUp::WidePaddle
+            | PowerUp::SlowBall
+            | PowerUp::ExtraLife
+            | PowerUp::MultiBall
+            | PowerUp::Laser
+            | PowerUp::Catch => None,
+        }
+    }
+}
+
+/// A timed power-up that is currently active.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Effect {
+    power_up: PowerUp,
+    remaining: f32,
+}
+
+impl Effect {
+    pub(crate) fn new(power_up: PowerUp, duration: f32) -> Self {
+        Self {
+            power_up,
+            remaining: duration,
+        }
+    }
+
+    pub fn power_up(&self) -> PowerUp {
+        self.power_up
+    }
+
+    /// Seconds until the effect wears off.
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    pub(crate) fn restart(&mut self, duration: f32) {
+        self.remaining = duration;
+    }
+
+    /// Runs the effect's timer down by `dt`, and returns whether it has worn off.
+    pub(crate) fn tick(&mut self, dt: f32) -> bool {
+        self.remaining -= dt;
+        self.remaining <= 0.
+    }
+}
+
+/// A power-up falling from a destroyed block. It takes effect if it lands on the paddle.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Capsule {
+    power_up: PowerUp,
+    origin: vec2,
+}
+
+impl Capsule {
+    /// Creates a capsule centered on `center`.
+    pub fn new(power_up: PowerUp, center: vec2) -> Self {
+        let [width, height] = CAPSULE_DIMENSIONS;
+        Self {
+            power_up,
+            origin: [center.x() - width * 0.5, center.y() - height * 0.5],
+        }
+    }
+
+    pub fn power_up(&self) -> PowerUp {
+        self.power_up
+    }
+
+    pub fn dimensions(&self) -> vec2 {
+        CAPSULE_DIMENSIONS
+    }
+}
+
+impl Shape for Capsule {
+    fn bounding_box(&self) -> Box {
+        Box {
+            left: self.left(),
+            right: self.right(),
+            bottom: self.bottom(),
+            top: self.top(),
+        }
+    }
+}
+
+impl Rectangle for Capsule {
+    fn dimensions(&self) -> vec2 {
+        CAPSULE_DIMENSIONS
+    }
+
+    fn origin(&self) -> vec2 {
+        self.origin
+    }
+}
+
+impl GameObject for Capsule {
+    fn location(&self) -> vec2 {
+        self.origin
+    }
+
+    fn velocity(&self) -> vec2 {
+        [0., -CAPSULE_SPEED]
+    }
+
+    fn set_location(&mut self, location: vec2) {
+        self.origin = location
+    }
+
+    fn set_velocity(&mut self, _: vec2) {
+        panic!("Capsules always fall at the same speed.");
+    }
+}
+
+/// Decides what, if anything, a destroyed block drops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropTable {
+    /// The chance, from 0 to 1, that a destroyed block drops anything at all.
+    chance: f32,
+
+    /// Each power-up that can drop and how likely it is relative to the others.
+    weights: Vec<(PowerUp, u32)>,
+}
+
+impl DropTable {
+    /// A table where each destroyed block has `chance` of dropping something. Add the power-ups
+    /// that can drop with `with`.
+    pub fn new(chance: f32) -> Self {
+        Self {
+            chance,
+            weights: vec![],
+        }
+    }
+
+    /// A table that never drops anything.
+    pub fn empty() -> Self {
+        Self::new(0.)
+    }
+
+    /// Adds `power_up` to the table. It drops `weight` times as often as a power-up of weight 1.
+    pub fn with(mut self, power_up: PowerUp, weight: u32) -> Self {
+        self.weights.push((power_up, weight));
+        self
+    }
+
+    pub fn chance(&self) -> f32 {
+        self.chance
+    }
+
+    pub fn weights(&self) -> &[(PowerUp, u32)] {
+        &self.weights[..]
+    }
+
+    /// Picks what a destroyed block drops.
+    pub(crate) fn roll<R: Rng>(&self, rng: &mut R) -> Option<PowerUp> {
+        let total: u32 = self.weights.iter().map(|&(_, weight)| weight).sum();
+        if total == 0 || self.chance <= 0. {
+            return None;
+        }
+
+        if rng.gen::<f32>() >= self.chance {
+            return None;
+        }
+
+        let mut pick = rng.gen_range(0, total);
+        for &(power_up, weight) in &self.weights {
+            if pick < weight {
+                return Some(power_up);
+            }
+            pick -= weight;
+        }
+
+        None
+    }
+}
+
+impl Default for DropTable {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
@@ -0,0 +1,68 @@
+use prelude::*;
+use shape::Box;
+
+/// How fast projectiles fly, in units per second.
+pub(crate) const PROJECTILE_SPEED: f32 = 1.2;
+
+/// The size of every projectile.
+const PROJECTILE_DIMENSIONS: vec2 = [0.006, 0.03];
+
+/// A laser bolt fired upward from the paddle. It damages the first block it runs into, and is
+/// gone once it reaches the top of the arena.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Projectile {
+    origin: vec2,
+}
+
+impl Projectile {
+    /// Creates a projectile centered on `center`.
+    pub fn new(center: vec2) -> Self {
+        let [width, height] = PROJECTILE_DIMENSIONS;
+        Self {
+            origin: [center.x() - width * 0.5, center.y() - height * 0.5],
+        }
+    }
+
+    pub fn dimensions(&self) -> vec2 {
+        PROJECTILE_DIMENSIONS
+    }
+}
+
+impl Shape for Projectile {
+    fn bounding_box(&self) -> Box {
+        Box {
+            left: self.left(),
+            right: self.right(),
+            bottom: self.bottom(),
+            top: self.top(),
+        }
+    }
+}
+
+impl Rectangle for Projectile {
+    fn dimensions(&self) -> vec2 {
+        PROJECTILE_DIMENSIONS
+    }
+
+    fn origin(&self) -> vec2 {
+        self.origin
+    }
+}
+
+impl GameObject for Projectile {
+    fn location(&self) -> vec2 {
+        self.origin
+    }
+
+    fn velocity(&self) -> vec2 {
+        [0., PROJECTILE_SPEED]
+    }
+
+    fn set_location(&mut self, location: vec2) {
+        self.origin = location
+    }
+
+    fn set_velocity(&mut self, _: vec2) {
+        panic!("Projectiles always fly at the same speed.");
+    }
+}
@@ -0,0 +1,230 @@
+//! Replays are written as a small header, the level in the level format, and then the commands
+//! with runs of identical commands written once:
+//!
+//! ```text
+//! dt: 0.0010416667
+//! points: 0
+//! spin: 0
+//! level:
+//! lives: 3
+//! seed: 0
+//! ...
+//! commands:
+//! 480 0
+//! 1 0 launch
+//! 200 -1
+//! 1 -1 fire pause
+//! ```
+//!
+//! Each command line is how many ticks it lasts, the steering, and then any buttons pressed.
+
+use level::Words;
+use prelude::*;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The most ticks a replay can have when it is read, about three hours at 960 ticks a second.
+/// Every tick takes memory, so a typo in a tick count shouldn't be able to ask for terabytes.
+const MAX_REPLAY_TICKS: usize = 10_000_000;
+
+/// A game's starting state and the command given on every tick, which is all it takes to play
+/// the game again exactly as it went.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    dt: f32,
+    points: u32,
+
+    /// The ball's spin at the start, which the level format leaves out.
+    spin: f32,
+    level: Level,
+    commands: Vec<Command>,
+}
+
+impl Replay {
+    /// Starts recording `game`, which shouldn't have been ticked yet.
+    pub fn new(game: &Breakout) -> Self {
+        let builder = game.to_builder();
+        Self {
+            dt: game.dt(),
+            points: builder.points,
+            spin: builder.ball.map_or(0., |ball| ball.spin()),
+            level: Level::new(LevelInfo::default(), builder),
+            commands: vec![],
+        }
+    }
+
+    /// Adds the command for the next tick.
+    pub fn record(&mut self, command: Command) {
+        self.commands.push(command);
+    }
+
+    pub fn commands(&self) -> &[Command] {
+        &self.commands[..]
+    }
+
+    /// The game as it was when recording started.
+    pub fn game(&self) -> Breakout {
+        self.builder().build()
+    }
+
+    fn builder(&self) -> BreakoutBuilder {
+        let mut builder = self.level.builder().dt(self.dt).points(self.points);
+        if let Some(ref mut ball) = builder.ball {
+            ball.set_spin(self.spin);
+        }
+        builder
+    }
+
+    /// Plays every command back and returns the game as it was at the end.
+    pub fn play(&self) -> Breakout {
+        let mut game = self.game();
+        for &command in &self.commands {
+            game.apply(command);
+            game.tick();
+        }
+        game
+    }
+
+    pub fn parse(text: &str) -> Result<Self, LevelError> {
+        let lines: Vec<&str> = text.lines().collect();
+        let find = |section: &str, from: usize| {
+            lines[from..]
+                .iter()
+                .position(|line| line.trim() == section)
+                .map(|position| from + position)
+                .ok_or_else(|| LevelError::new(lines.len(), format!("missing `{}`", section)))
+        };
+        let level_start = find("level:", 0)?;
+        let commands_start = find("commands:", level_start)?;
+
+        let mut dt = None;
+        let mut points = 0;
+        let mut spin = 0.;
+        for (number, line) in lines[..level_start].iter().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let (key, value) = match line.find(':') {
+                Some(colon) => (line[..colon].trim(), line[colon + 1..].trim()),
+                None => return Err(LevelError::new(number + 1, "expected `key: value`")),
+            };
+
+            let mut words = Words::new(number + 1, value);
+            match key {
+                "dt" => dt = Some(words.next()?),
+                "points" => points = words.next()?,
+                "spin" => spin = words.next()?,
+                _ => return Err(words.error(format!("unknown key `{}`", key))),
+            }
+            words.finish()?;
+        }
+        let dt = dt.ok_or_else(|| LevelError::new(level_start + 1, "missing `dt`"))?;
+
+        // Errors in the level are counted from the start of the level, not of the replay.
+        let level_text = lines[level_start + 1..commands_start].join("\n");
+        let level = Level::parse(&level_text)
+            .map_err(|error| LevelError::new(error.line() + level_start + 1, error.message()))?;
+
+        let commands = parse_commands(&lines[commands_start + 1..], commands_start + 1)?;
+
+        let replay = Self {
+            dt,
+            points,
+            spin,
+            level,
+            commands,
+        };
+
+        // Checked here so that `game` and `play` can't fail later on.
+        replay
+            .builder()
+            .try_build()
+            .map_err(|error| LevelError::new(level_start + 1, error.to_string()))?;
+
+        Ok(replay)
+    }
+
+    /// Reads commands written the way a replay writes them, without the rest of the replay.
+    /// Handy for scripting the input to a game by hand.
+    pub fn parse_commands(text: &str) -> Result<Vec<Command>, LevelError> {
+        let lines: Vec<&str> = text.lines().collect();
+        parse_commands(&lines, 0)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "dt: {}", self.dt)?;
+        writeln!(f, "points: {}", self.points)?;
+        writeln!(f, "spin: {}", self.spin)?;
+        writeln!(f, "level:")?;
+        self.level.write(f, false)?;
+        writeln!(f, "commands:")?;
+
+        let mut commands = self.commands.iter().peekable();
+        while let Some(&command) = commands.next() {
+            let mut ticks = 1;
+            while commands.peek() == Some(&&command) {
+                commands.next();
+                ticks += 1;
+            }
+
+            write!(f, "{} {}", ticks, command.steer)?;
+            if command.launch {
+                write!(f, " launch")?;
+            }
+            if command.fire {
+                write!(f, " fire")?;
+            }
+            if command.pause {
+                write!(f, " pause")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads command lines, where the first of `lines` is line `skipped + 1` of the file.
+fn parse_commands(lines: &[&str], skipped: usize) -> Result<Vec<Command>, LevelError> {
+    let mut commands = vec![];
+    for (number, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        let mut words = Words::new(skipped + number + 1, line);
+        let ticks: usize = words.next()?;
+        if ticks > MAX_REPLAY_TICKS - commands.len() {
+            return Err(words.error(format!(
+                "replays can be at most {} ticks long",
+                MAX_REPLAY_TICKS
+            )));
+        }
+        let mut command = Command::steer(words.next()?);
+        while let Some(button) = words.try_next_word() {
+            match button {
+                "launch" => command.launch = true,
+                "fire" => command.fire = true,
+                "pause" => command.pause = true,
+                _ => return Err(words.error(format!("unknown button `{}`", button))),
+            }
+        }
+        commands.extend((0..ticks).map(|_| command));
+    }
+    Ok(commands)
+}
@@ -0,0 +1,101 @@
+/// The combo multiplier stops growing once it gets this high.
+const MAX_MULTIPLIER: u32 = 8;
+
+/// Clearing a level faster than this many seconds earns a bonus, unless the level sets its own
+/// par time.
+const PAR_TIME: f32 = 60.;
+
+/// Bonus points for every second a level is cleared under par.
+const TIME_BONUS_PER_SECOND: f32 = 50.;
+
+/// Keeps track of the player's points. Every block the ball destroys without touching the paddle
+/// in between is worth more than the last.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Score {
+    points: u32,
+
+    /// Blocks destroyed since the ball last touched the paddle.
+    combo: u32,
+
+    /// Seconds spent on the current level, not counting time paused.
+    elapsed: f32,
+
+    /// How many seconds the level should take. Clearing it faster earns a bonus.
+    par_time: f32,
+}
+
+impl Score {
+    pub fn new() -> Self {
+        Self::with_par_time(PAR_TIME)
+    }
+
+    pub fn with_par_time(par_time: f32) -> Self {
+        Self {
+            points: 0,
+            combo: 0,
+            elapsed: 0.,
+            par_time,
+        }
+    }
+
+    /// Starts the count at `points`, for a level that carries on from an earlier one.
+    pub(crate) fn starting_at(mut self, points: u32) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// What the next destroyed block's points will be multiplied by.
+    pub fn multiplier(&self) -> u32 {
+        (self.combo + 1).min(MAX_MULTIPLIER)
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn par_time(&self) -> f32 {
+        self.par_time
+    }
+
+    pub(crate) fn tick(&mut self, dt: f32) {
+        self.elapsed += dt;
+    }
+
+    /// Awards a destroyed block's points, multiplied by the combo, and returns how many were
+    /// awarded.
+    pub(crate) fn block_destroyed(&mut self, block_points: u32) -> u32 {
+        let points = block_points.saturating_mul(self.multiplier());
+        self.points = self.points.saturating_add(points);
+        self.combo += 1;
+        points
+    }
+
+    pub(crate) fn paddle_hit(&mut self) {
+        self.combo = 0;
+    }
+
+    pub(crate) fn ball_lost(&mut self) {
+        self.combo = 0;
+    }
+
+    /// Awards the bonus for clearing the level quickly and returns how many points it was worth.
+    pub(crate) fn level_cleared(&mut self) -> u32 {
+        let bonus = ((self.par_time - self.elapsed).max(0.) * TIME_BONUS_PER_SECOND) as u32;
+        self.points = self.points.saturating_add(bonus);
+        bonus
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self::new()
+    }
+}
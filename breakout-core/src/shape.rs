@@ -7,6 +7,29 @@ pub struct Box {
     pub top: f32,
 }
 
+/// The side of a rectangle that something touched.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Face {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Corner,
+}
+
+impl Face {
+    /// Classifies the unit normal of a contact against an axis-aligned rectangle.
+    pub fn from_normal(normal: vec2) -> Face {
+        match (normal.x(), normal.y()) {
+            (x, y) if x == 0. && y > 0. => Face::Top,
+            (x, y) if x == 0. && y < 0. => Face::Bottom,
+            (x, y) if x < 0. && y == 0. => Face::Left,
+            (x, y) if x > 0. && y == 0. => Face::Right,
+            _ => Face::Corner,
+        }
+    }
+}
+
 pub trait Shape {
     fn bounding_box(&self) -> Box;
 }
@@ -30,6 +53,13 @@ pub trait Rectangle {
     fn top(&self) -> f32 {
         self.bottom() + self.dimensions().y()
     }
+
+    fn center(&self) -> vec2 {
+        [
+            self.left() + self.dimensions().x() * 0.5,
+            self.bottom() + self.dimensions().y() * 0.5,
+        ]
+    }
 }
 
 pub trait Circle {
@@ -0,0 +1,226 @@
+use super::super::{
+    Ball, Block, BlockKind, Breakout, BreakoutBuilder, BuildError, DropTable, Paddle, PhysicsConfig,
+};
+
+fn level() -> BreakoutBuilder {
+    BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.3], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+}
+
+#[test]
+fn missing_parts_are_errors() {
+    let error = BreakoutBuilder::new()
+        .ball(Ball::new(0.01, [0.5, 0.3], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::MissingDt);
+
+    let error = BreakoutBuilder::new().dt(0.01).try_build().unwrap_err();
+    assert_eq!(error, BuildError::MissingBall);
+}
+
+#[test]
+fn bad_sizes_are_errors() {
+    let error = level().dt(f32::NAN).try_build().unwrap_err();
+    assert!(matches!(error, BuildError::InvalidDt(_)));
+
+    let error = level()
+        .ball(Ball::new(0., [0.5, 0.3], [0., 0.5]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::InvalidBallRadius(0.));
+
+    let error = level()
+        .add_block(Block::new([0.1, -0.05], [0.1, 0.8]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        BuildError::InvalidBlockDimensions {
+            index: 0,
+            dimensions: [0.1, -0.05],
+        }
+    );
+}
+
+#[test]
+fn games_need_lives_and_working_physics() {
+    let error = level().lives(0).try_build().unwrap_err();
+    assert_eq!(error, BuildError::NoLives);
+
+    let error = level()
+        .physics(PhysicsConfig {
+            max_ball_speed: 0.,
+            ..PhysicsConfig::new()
+        })
+        .try_build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        BuildError::InvalidPhysics {
+            name: "max_ball_speed",
+            value: 0.,
+        }
+    );
+
+    let error = level()
+        .physics(PhysicsConfig {
+            paddle_speed: f32::INFINITY,
+            ..PhysicsConfig::new()
+        })
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::InvalidPhysics {
+            name: "paddle_speed",
+            ..
+        }
+    ));
+
+    let error = level()
+        .physics(PhysicsConfig {
+            wall_restitution: -0.5,
+            ..PhysicsConfig::new()
+        })
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::InvalidPhysics {
+            name: "wall_restitution",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn ball_has_to_be_able_to_move() {
+    let error = level()
+        .ball(Ball::new(0.01, [0.5, 0.3], [0., f32::NAN]))
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(error, BuildError::InvalidBallVelocity(_)));
+
+    let still = Ball::new(0.01, [0.5, 0.3], [0., 0.]);
+    assert!(level().ball(still).try_build().is_ok());
+    let error = level().ball(still).serve().try_build().unwrap_err();
+    assert_eq!(error, BuildError::NoLaunchSpeed);
+}
+
+#[test]
+fn blocks_need_hit_points() {
+    let error = level()
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.8]))
+        .add_block(Block::new([0.1, 0.05], [0.3, 0.8]).with_hit_points(0))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::NoHitPoints { index: 1 });
+}
+
+#[test]
+fn block_kinds_need_sensible_values() {
+    for &kind in &[
+        BlockKind::Armored {
+            min_speed: f32::NAN,
+        },
+        BlockKind::Armored { min_speed: -1. },
+        BlockKind::Explosive { radius: -0.1 },
+        BlockKind::Explosive {
+            radius: f32::INFINITY,
+        },
+    ] {
+        let error = level()
+            .add_block(Block::new([0.1, 0.05], [0.1, 0.8]).with_kind(kind))
+            .try_build()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            BuildError::InvalidBlockKind { index: 0, .. }
+        ));
+    }
+}
+
+#[test]
+fn drop_chance_has_to_be_a_probability() {
+    for &chance in &[f32::NAN, -0.1, 1.5] {
+        let error = level()
+            .drop_table(DropTable::new(chance))
+            .try_build()
+            .unwrap_err();
+        assert!(matches!(error, BuildError::InvalidDropChance(_)));
+    }
+    assert!(level().drop_table(DropTable::new(1.)).try_build().is_ok());
+}
+
+#[test]
+fn par_time_has_to_be_a_time() {
+    for &par_time in &[f32::NAN, f32::INFINITY, -1.] {
+        let error = level().par_time(par_time).try_build().unwrap_err();
+        assert!(matches!(error, BuildError::InvalidParTime(_)));
+    }
+    assert!(level().par_time(0.).try_build().is_ok());
+}
+
+#[test]
+fn everything_has_to_fit_in_the_arena() {
+    let error = level()
+        .paddle(Paddle::new([0.2, 0.02], [0.9, 0.1]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::PaddleOutsideArena);
+
+    let error = level()
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.8]))
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.98]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::BlockOutsideArena { index: 1 });
+}
+
+#[test]
+fn blocks_can_touch_but_not_overlap() {
+    assert!(level()
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.8]))
+        .add_block(Block::new([0.1, 0.05], [0.2, 0.8]))
+        .try_build()
+        .is_ok());
+
+    let error = level()
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.8]))
+        .add_block(Block::new([0.1, 0.05], [0.5, 0.8]))
+        .add_block(Block::new([0.1, 0.05], [0.15, 0.82]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        BuildError::OverlappingBlocks {
+            first: 0,
+            second: 2
+        }
+    );
+}
+
+#[test]
+fn ball_cannot_start_inside_anything() {
+    let error = level()
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.28]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::BallInsideBlock { index: 0 });
+
+    let error = level()
+        .ball(Ball::new(0.01, [0.5, 0.125], [0., 0.5]))
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, BuildError::BallInsidePaddle);
+}
+
+#[test]
+fn built_in_level_is_valid() {
+    let game = Breakout::level_1(0.01);
+    assert_eq!(game.blocks().len(), 4);
+}
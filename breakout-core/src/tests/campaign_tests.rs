@@ -0,0 +1,150 @@
+use super::super::{BuildError, Campaign, CampaignError, GameEvent, Level, Phase, Progress};
+use std::env;
+use std::fs;
+
+/// A level the ball clears on its own: it starts moving straight up into the only block.
+fn quick_level(lives: u32) -> Level {
+    Level::parse(&format!(
+        "lives: {}
+ball: 0.01 0.5 0.5 0 1
+paddle: 0.2 0.02 0.4 0.1
+block: 0.1 0.05 0.45 0.6 normal
+",
+        lives
+    ))
+    .unwrap()
+}
+
+fn tick_until_cleared(campaign: &mut Campaign) -> Vec<GameEvent> {
+    for _ in 0..1000 {
+        let events = campaign.tick();
+        if events
+            .iter()
+            .any(|event| matches!(event, GameEvent::LevelCleared { .. }))
+        {
+            return events;
+        }
+    }
+    panic!("level was never cleared");
+}
+
+#[test]
+fn clearing_a_level_moves_on_to_the_next() {
+    let mut campaign = Campaign::new(0.01, vec![quick_level(5), quick_level(2)]).unwrap();
+    assert_eq!(campaign.level(), 0);
+    assert_eq!(campaign.game().lives(), 5);
+
+    tick_until_cleared(&mut campaign);
+    let points = campaign.game().score().points();
+    assert!(points > 0);
+
+    // The next level keeps the points and lives, not its own lives.
+    assert_eq!(campaign.level(), 1);
+    assert_eq!(campaign.game().phase(), Phase::Playing);
+    assert_eq!(campaign.game().lives(), 5);
+    assert!(campaign.game().blocks().iter().all(|block| block.is_some()));
+    assert_eq!(
+        campaign.progress(),
+        Progress {
+            level: 1,
+            points,
+            lives: 5,
+            checksum: Campaign::checksum(campaign.levels()),
+        }
+    );
+    assert!(!campaign.is_finished());
+
+    tick_until_cleared(&mut campaign);
+    assert_eq!(campaign.level(), 1);
+    assert_eq!(campaign.game().phase(), Phase::LevelCleared);
+    assert!(campaign.game().score().points() > points);
+    assert!(campaign.is_finished());
+}
+
+#[test]
+fn restarting_a_level_goes_back_to_how_it_started() {
+    let levels = vec![quick_level(3), quick_level(3)];
+    let progress = Progress {
+        level: 1,
+        points: 1234,
+        lives: 2,
+        checksum: Campaign::checksum(&levels),
+    };
+    let mut campaign = Campaign::resume(0.01, levels, progress).unwrap();
+    assert_eq!(campaign.game().score().points(), 1234);
+    assert_eq!(campaign.game().lives(), 2);
+
+    tick_until_cleared(&mut campaign);
+    campaign.restart_level();
+    assert_eq!(campaign.game().phase(), Phase::Playing);
+    assert_eq!(campaign.game().score().points(), 1234);
+}
+
+#[test]
+fn progress_is_saved_and_loaded() {
+    let progress = Progress {
+        level: 3,
+        points: 4500,
+        lives: 1,
+        checksum: 987654321,
+    };
+    assert_eq!(Progress::parse(&progress.to_string()), Ok(progress));
+
+    let path = env::temp_dir().join(format!("breakout-progress-{}.txt", std::process::id()));
+    progress.save(&path).unwrap();
+    let loaded = Progress::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), progress);
+
+    let error = Progress::parse("level: 1\npoints: lots\nlives: 2\nchecksum: 1\n").unwrap_err();
+    assert_eq!(error.line(), 2);
+    assert!(Progress::parse("level: 1\n").is_err());
+}
+
+#[test]
+fn progress_has_to_fit_the_levels() {
+    let levels = vec![quick_level(3), quick_level(3)];
+    let progress = Progress {
+        level: 1,
+        points: 0,
+        lives: 3,
+        checksum: Campaign::checksum(&levels),
+    };
+
+    assert_eq!(
+        Campaign::new(0.01, vec![]).unwrap_err(),
+        CampaignError::NoLevels
+    );
+
+    // Progress saved for two levels doesn't belong to either one of them on its own.
+    let error = Campaign::resume(0.01, vec![quick_level(3)], progress).unwrap_err();
+    assert_eq!(error, CampaignError::WrongLevels);
+
+    let error = Campaign::resume(
+        0.01,
+        levels.clone(),
+        Progress {
+            level: 2,
+            ..progress
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        CampaignError::NoSuchLevel {
+            level: 2,
+            levels: 2
+        }
+    );
+
+    let error = Campaign::resume(
+        0.01,
+        levels,
+        Progress {
+            lives: 0,
+            ..progress
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, CampaignError::InvalidProgress(BuildError::NoLives));
+}
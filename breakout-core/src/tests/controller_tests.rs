@@ -0,0 +1,79 @@
+use super::super::{
+    Ball, Block, Breakout, BreakoutBuilder, GameEvent, GameObject, Paddle, PaddleController, Phase,
+    PredictiveController, TrackingController,
+};
+
+/// Lets `controller` play `game` for up to `seconds`, and returns the game and how many balls it
+/// lost.
+fn play<C: PaddleController>(
+    mut controller: C,
+    mut game: Breakout,
+    seconds: f32,
+) -> (Breakout, u32) {
+    let mut lost = 0;
+    for _ in 0..(seconds / game.dt()) as usize {
+        let command = controller.command(&game);
+        game.apply(command);
+        lost += game
+            .tick()
+            .iter()
+            .filter(|&&event| event == GameEvent::BallLost)
+            .count() as u32;
+
+        if game.phase() == Phase::LevelCleared {
+            break;
+        }
+    }
+    (game, lost)
+}
+
+#[test]
+fn prediction_follows_the_ball_off_the_walls() {
+    // Heads up and to the right, bouncing off of the right wall and then the top.
+    let game = BreakoutBuilder::new()
+        .dt(0.001)
+        .ball(Ball::new(0.02, [0.8, 0.5], [0.5, 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.1, 0.1]))
+        .add_block(Block::new([0.05, 0.05], [0.01, 0.94]))
+        .build();
+    let y = 0.2;
+    let (x, time) = PredictiveController::predict(&game, game.ball(), y).unwrap();
+
+    let mut game = game;
+    let mut elapsed = 0.;
+    while game.ball().location()[1] > y || game.ball().velocity()[1] > 0. {
+        game.tick();
+        elapsed += game.dt();
+    }
+
+    assert!((game.ball().location()[0] - x).abs() < 0.01);
+    assert!((elapsed - time).abs() < 0.01);
+}
+
+#[test]
+fn predictive_controller_clears_a_level() {
+    let (game, lost) = play(
+        PredictiveController::new(),
+        Breakout::level_1(1. / 960.),
+        120.,
+    );
+    assert_eq!(game.phase(), Phase::LevelCleared);
+    assert_eq!(lost, 0);
+}
+
+#[test]
+fn tracking_controller_keeps_a_slow_ball_up() {
+    let game = BreakoutBuilder::new()
+        .dt(1. / 960.)
+        .ball(Ball::new(0.015, [0.3, 0.5], [0.2, -0.3]))
+        .paddle(Paddle::new([0.15, 0.02], [0.425, 0.065]))
+        .add_block(Block::new([0.05, 0.05], [0.9, 0.9]).with_hit_points(1000))
+        .build();
+
+    // The whole 20 seconds is played without the ball getting past the paddle.
+    let (game, lost) = play(TrackingController::new(), game, 20.);
+    assert_eq!(lost, 0);
+    assert_eq!(game.lives(), 3);
+    assert_eq!(game.phase(), Phase::Playing);
+    assert!(game.score().elapsed() > 19.9);
+}
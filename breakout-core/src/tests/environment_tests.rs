@@ -0,0 +1,148 @@
+use super::super::{
+    Action, ActionError, Ball, Block, Breakout, BreakoutBuilder, BuildError, Environment,
+    GameEvent, Paddle, PaddleController, Phase, PredictiveController,
+};
+
+fn level() -> BreakoutBuilder {
+    Breakout::level_1(1. / 960.).to_builder()
+}
+
+#[test]
+fn observations_describe_the_game() {
+    let mut environment = Environment::new(
+        BreakoutBuilder::new()
+            .dt(0.001)
+            .ball(Ball::new(0.02, [0.5, 0.5], [0.1, -0.2]))
+            .paddle(Paddle::new([0.2, 0.02], [0.3, 0.1]))
+            .add_block(Block::new([0.1, 0.05], [0.1, 0.9]).with_hit_points(2))
+            .add_block(Block::new([0.1, 0.05], [0.3, 0.9]))
+            .serve(),
+    )
+    .unwrap();
+
+    let observation = environment.reset(0);
+    assert_eq!(observation.len(), environment.observation_len());
+    assert_eq!(observation.len(), 10);
+
+    // The served ball sits on the middle of the paddle.
+    assert!((observation[0] - 0.4).abs() < 1e-6);
+    assert_eq!(observation[2], 0.2);
+    assert!((observation[3] - 0.4).abs() < 1e-6);
+    assert_eq!(observation[7], 1.);
+    assert_eq!(&observation[8..], &[1., 1.]);
+}
+
+#[test]
+fn same_seed_and_actions_play_out_the_same() {
+    let mut environment = Environment::new(level()).unwrap().frame_skip(4);
+    let actions = [
+        Action::Discrete(3),
+        Action::Discrete(1),
+        Action::Continuous(0.5),
+        Action::Discrete(2),
+    ];
+
+    let episode = |environment: &mut Environment| {
+        let mut steps = vec![environment.reset(7)];
+        for i in 0..2000 {
+            let step = environment.step(actions[i % actions.len()]).unwrap();
+            steps.push(step.observation);
+            if step.done {
+                break;
+            }
+        }
+        steps
+    };
+
+    let first = episode(&mut environment);
+    let second = episode(&mut environment);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn frame_skip_repeats_the_action() {
+    let mut environment = Environment::new(level()).unwrap().frame_skip(8);
+    environment.reset(0);
+
+    let step = environment.step(Action::Discrete(3)).unwrap();
+    assert_eq!(step.info.ticks, 8);
+    assert_eq!(step.info.events[0], GameEvent::BallLaunched);
+    assert_eq!(environment.game().phase(), Phase::Playing);
+
+    let x = environment.observation()[0];
+    let step = environment.step(Action::Discrete(2)).unwrap();
+    assert!(step.observation[0] > x);
+    assert!(!step.done);
+}
+
+#[test]
+fn rewards_add_up_to_the_score() {
+    let mut environment = Environment::new(level())
+        .unwrap()
+        .frame_skip(4)
+        .max_steps(200_000);
+    environment.reset(3);
+
+    // The predictive controller is good enough to clear the level on its own.
+    let mut controller = PredictiveController::new();
+    let mut reward = 0.;
+    loop {
+        let steer = controller.command(environment.game()).steer;
+        let step = environment.step(Action::Continuous(steer)).unwrap();
+        reward += step.reward;
+        if step.done {
+            assert!(!step.info.truncated);
+            assert_eq!(step.info.points as f32, reward);
+            break;
+        }
+    }
+
+    assert_eq!(environment.game().phase(), Phase::LevelCleared);
+    assert!(environment.observation()[8..]
+        .iter()
+        .zip(environment.game().blocks())
+        .all(|(&health, block)| block
+            .as_ref()
+            .map_or(health == 0., |block| !block.is_destructible())));
+}
+
+#[test]
+fn episodes_can_be_cut_short() {
+    let mut environment = Environment::new(level()).unwrap().max_steps(10);
+    environment.reset(0);
+
+    for _ in 0..9 {
+        assert!(!environment.step(Action::Discrete(0)).unwrap().done);
+    }
+    let step = environment.step(Action::Discrete(0)).unwrap();
+    assert!(step.done);
+    assert!(step.info.truncated);
+
+    environment.reset(0);
+    assert!(!environment.step(Action::Discrete(0)).unwrap().done);
+}
+
+#[test]
+fn bad_actions_are_errors() {
+    let mut environment = Environment::new(level()).unwrap();
+    let observation = environment.reset(0);
+
+    assert_eq!(
+        environment.step(Action::Discrete(Environment::DISCRETE_ACTIONS)),
+        Err(ActionError::NoSuchAction(Environment::DISCRETE_ACTIONS))
+    );
+    assert!(matches!(
+        environment.step(Action::Continuous(f32::NAN)),
+        Err(ActionError::InvalidSteering(_))
+    ));
+    assert_eq!(environment.observation(), observation);
+}
+
+#[test]
+fn blocks_without_hit_points_are_rejected() {
+    // Their share of the observation would be 0 out of 0.
+    let error =
+        Environment::new(level().add_block(Block::new([0.1, 0.05], [0.4, 0.5]).with_hit_points(0)))
+            .unwrap_err();
+    assert_eq!(error, BuildError::NoHitPoints { index: 4 });
+}
@@ -0,0 +1,153 @@
+use super::super::colors::{block_color, BALL_COLOR, BLOCK_COLOR, PADDLE_COLOR};
+use super::super::{Ball, Block, BlockKind, Breakout, BreakoutBuilder, Frame, Paddle, PixelFormat};
+
+fn game() -> Breakout {
+    BreakoutBuilder::new()
+        .dt(0.001)
+        .ball(Ball::new(0.1, [0.5, 0.5], [0., 0.]))
+        .paddle(Paddle::new([0.4, 0.1], [0.1, 0.]))
+        .add_block(Block::new([0.3, 0.1], [0.6, 0.8]))
+        .add_block(
+            Block::new([0.3, 0.1], [0.1, 0.8])
+                .with_kind(BlockKind::Steel)
+                .with_hit_points(1),
+        )
+        .build()
+}
+
+fn rgb(color: [f32; 3]) -> Vec<u8> {
+    color
+        .iter()
+        .map(|&value| (value * 255.).round() as u8)
+        .collect()
+}
+
+#[test]
+fn objects_are_drawn_in_their_colors() {
+    let game = game();
+    let frame = Frame::render(&game, 20, 20, PixelFormat::Rgb);
+    assert_eq!(frame.pixels().len(), 20 * 20 * 3);
+
+    assert_eq!(frame.pixel(0, 0), &[0, 0, 0]);
+    assert_eq!(frame.pixel(10, 10), &rgb(BALL_COLOR)[..]);
+    assert_eq!(frame.pixel(5, 19), &rgb(PADDLE_COLOR)[..]);
+    assert_eq!(frame.pixel(15, 3), &rgb(BLOCK_COLOR)[..]);
+    assert_eq!(
+        frame.pixel(5, 3),
+        &rgb(block_color(game.blocks()[1].as_ref().unwrap()))[..]
+    );
+}
+
+#[test]
+fn balls_are_discs() {
+    let frame = Frame::render(&game(), 20, 20, PixelFormat::Rgb);
+    let red = rgb(BALL_COLOR);
+
+    // The corners of the square around the ball are left empty.
+    assert_eq!(frame.pixel(8, 8), &[0, 0, 0]);
+    assert_eq!(frame.pixel(9, 9), &red[..]);
+
+    let ball_pixels = (0..20)
+        .flat_map(|y| (0..20).map(move |x| (x, y)))
+        .filter(|&(x, y)| frame.pixel(x, y) == &red[..])
+        .count();
+    assert_eq!(ball_pixels, 12);
+}
+
+#[test]
+fn grayscale_is_brightness() {
+    let game = game();
+    let gray = Frame::render(&game, 20, 20, PixelFormat::Grayscale);
+    assert_eq!(gray.pixels().len(), 20 * 20);
+
+    assert_eq!(gray.pixel(0, 0), &[0]);
+    assert_eq!(gray.pixel(5, 19), &[255]);
+    assert_eq!(gray.pixel(10, 10), &[76]);
+}
+
+/// Level 1 at 80 by 50 pixels: the four blocks, and the ball served from the paddle.
+const LEVEL_1: &str = "\
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+............########........########........########........########............
+............########........########........########........########............
+............########........########........########........########............
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+................................................................................
+.......................................oo.......................................
+.......................................oo.......................................
+..................................============..................................
+................................................................................
+................................................................................
+................................................................................";
+
+#[test]
+fn level_1_matches_its_picture() {
+    let game = Breakout::level_1(1. / 960.);
+    let frame = Frame::render(&game, 80, 50, PixelFormat::Grayscale);
+
+    let picture: Vec<String> = frame
+        .pixels()
+        .chunks(frame.width())
+        .map(|row| {
+            row.iter()
+                .map(|&pixel| match pixel {
+                    0 => '.',
+                    255 => '=',
+                    76 => 'o',
+                    _ => '#',
+                })
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(picture.join("\n"), LEVEL_1);
+}
+
+#[test]
+fn frames_can_be_saved_as_images() {
+    let frame = Frame::render(&game(), 3, 2, PixelFormat::Grayscale);
+    let image = frame.to_pnm();
+    assert!(image.starts_with(b"P5\n3 2\n255\n"));
+    assert_eq!(image.len(), b"P5\n3 2\n255\n".len() + 6);
+
+    let frame = Frame::render(&game(), 3, 2, PixelFormat::Rgb);
+    assert!(frame.to_pnm().starts_with(b"P6\n3 2\n255\n"));
+}
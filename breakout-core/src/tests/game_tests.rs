@@ -1,30 +1,729 @@
-use super::super::{Ball, BreakoutBuilder, GameObject, Paddle};
+use super::super::{
+    Ball, Block, BlockKind, BreakoutBuilder, Face, GameEvent, GameObject, Paddle, Phase,
+    PhysicsConfig, Rectangle, Wall,
+};
+
+/// A block up in the corner, out of the ball's way, so that the level isn't already cleared.
+fn spare_block() -> Block {
+    Block::new([0.05, 0.02], [0., 0.98])
+}
 
 #[test]
 fn basic() {
     // Creates a Breakout game where the ball is directly above the paddle and traveling
     // perpendicular towards it at 0.1 units per second. Under our simple assumption that momentum
     // is completely conserved and the paddle is of infinite mass, we expect the ball to bounce
-    // back and travel away from the paddle at a rate of 0.1 units per second.
+    // back and travel away from the paddle at a rate of 0.1 units per second, plus the 5% speed up
+    // the ball gets from every paddle hit.
     let mut game = BreakoutBuilder::new()
         .dt(1. / 120.)
-        .ball(Ball::new(0.02, (0.5, 0.24), (0., -0.1)))
-        .paddle(Paddle::new((0.1, 0.04), (0.45, 0.18)))
+        .ball(Ball::new(0.02, [0.5, 0.24], [0., -0.1]))
+        .paddle(Paddle::new([0.1, 0.04], [0.45, 0.18]))
+        .add_block(spare_block())
         .build();
 
     // Run 120 frames - that should be 1 second, which should result in the ball being at
-    // (0.5, 0.34)
+    // (0.5, 0.345)
     for _ in 0..120 {
         game.tick();
     }
 
     let [ball_x, ball_y] = game.ball().location();
     assert!(
-        (ball_x - 0.5).abs() < 0.001 && (ball_y - 0.34).abs() < 0.001,
+        (ball_x - 0.5).abs() < 0.001 && (ball_y - 0.345).abs() < 0.001,
         "Ball was at location ({}, {}), but was expected at location ({}, {})",
         ball_x,
         ball_y,
         0.5,
-        0.34
+        0.345
+    );
+}
+
+#[test]
+fn fast_ball_does_not_tunnel_through_paddle() {
+    // The ball moves 0.5 units in a single tick, far more than the height of the paddle.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 10.)
+        .ball(Ball::new(0.01, [0.5, 0.5], [0., -5.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(spare_block())
+        .build();
+
+    game.tick();
+
+    let [_, ball_y] = game.ball().location();
+    let [_, ball_vy] = game.ball().velocity();
+    assert!(
+        ball_y > 0.12,
+        "Ball passed through the paddle to y = {}",
+        ball_y
+    );
+    assert!(ball_vy > 0., "Ball did not bounce off of the paddle");
+}
+
+#[test]
+fn fast_ball_does_not_tunnel_through_block() {
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 10.)
+        .ball(Ball::new(0.01, [0.5, 0.2], [0., 5.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.02], [0.45, 0.5]))
+        .build();
+
+    game.tick();
+
+    assert!(game.blocks()[0].is_none(), "Ball skipped over the block");
+}
+
+#[test]
+fn fast_ball_bounces_off_walls() {
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 10.)
+        .ball(Ball::new(0.01, [0.9, 0.5], [5., 0.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(spare_block())
+        .build();
+
+    game.tick();
+
+    let [ball_x, _] = game.ball().location();
+    let [ball_vx, _] = game.ball().velocity();
+    assert!(ball_x < 0.99, "Ball left the arena at x = {}", ball_x);
+    assert!(ball_vx < 0., "Ball did not bounce off of the wall");
+}
+
+#[test]
+fn ball_bounces_off_bottom_of_block() {
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.5, 0.4], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.5]))
+        .build();
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    let [_, ball_y] = game.ball().location();
+    let [_, ball_vy] = game.ball().velocity();
+    assert!(game.blocks()[0].is_none());
+    assert!(ball_vy < 0., "Ball did not bounce off of the block");
+    assert!(ball_y < 0.49, "Ball went into the block to y = {}", ball_y);
+}
+
+#[test]
+fn ball_only_hits_nearest_block() {
+    // Two blocks stacked on top of each other, with the ball heading straight up through the seam
+    // of the bottom row. Only one block should go before the ball turns around.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.5, 0.4], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.4, 0.5]))
+        .add_block(Block::new([0.1, 0.05], [0.5, 0.5]))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.55]))
+        .build();
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    let remaining = game.blocks().iter().filter(|b| b.is_some()).count();
+    assert_eq!(remaining, 2);
+    assert!(game.blocks()[2].is_some());
+}
+
+#[test]
+fn ball_bounces_off_block_corner() {
+    // Heads diagonally into the bottom left corner of the block, so it should come straight back.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.3, 0.3], [0.5, 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.5, 0.5]))
+        .build();
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    let [ball_vx, ball_vy] = game.ball().velocity();
+    assert!(game.blocks()[0].is_none());
+    assert!(
+        ball_vx < 0. && ball_vy < 0.,
+        "Ball did not bounce back off of the corner"
+    );
+}
+
+#[test]
+fn ball_bounces_off_side_of_paddle() {
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.2, 0.11], [0.5, 0.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(spare_block())
+        .build();
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    let [ball_x, _] = game.ball().location();
+    let [ball_vx, _] = game.ball().velocity();
+    assert!(
+        ball_vx < 0.,
+        "Ball did not bounce off of the side of the paddle"
     );
+    assert!(ball_x < 0.39, "Ball went into the paddle to x = {}", ball_x);
+}
+
+#[test]
+fn ball_deflects_off_paddle_corner() {
+    // Falls straight down onto the top left corner of the paddle, just outside of its span, so
+    // it should be knocked away to the left instead of falling through.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.395, 0.3], [0., -0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(spare_block())
+        .build();
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    let [ball_vx, ball_vy] = game.ball().velocity();
+    assert!(
+        ball_vx < 0. && ball_vy > 0.,
+        "Ball was not deflected by the corner"
+    );
+}
+
+#[test]
+fn ball_squeezed_between_paddle_and_wall() {
+    // The paddle runs the ball into the left wall. Neither should end up overlapping the other.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.02, [0.05, 0.11], [0., 0.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.1, 0.1]))
+        .add_block(spare_block())
+        .build();
+    game.paddle_mut().set_velocity([-0.7, 0.]);
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    let [ball_x, _] = game.ball().location();
+    let paddle_left = game.paddle().location()[0];
+    assert!(ball_x >= 0.02 - 1e-5, "Ball was pushed into the wall");
+    assert!(
+        paddle_left >= ball_x + 0.02 - 1e-5,
+        "Paddle overlapped the ball"
+    );
+}
+
+#[test]
+fn losing_one_of_several_balls_keeps_playing() {
+    // The ball is heading out of the bottom of the arena, but splitting it sends one of the new
+    // balls back up.
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.03], [0.5, -0.1]))
+        .paddle(Paddle::new([0.1, 0.02], [0.05, 0.5]))
+        .add_block(spare_block())
+        .build();
+
+    game.split_balls();
+    assert_eq!(game.balls().len(), 3);
+
+    let mut events = vec![];
+    for _ in 0..60 {
+        events.extend(game.tick());
+    }
+
+    assert_eq!(game.balls().len(), 1);
+    assert!(game.ball().velocity()[1] > 0.);
+    assert_eq!(game.lives(), 3);
+    assert_eq!(game.phase(), Phase::Playing);
+    assert_eq!(
+        events
+            .iter()
+            .filter(|&&event| event == GameEvent::ExtraBallLost)
+            .count(),
+        2
+    );
+    assert!(!events.contains(&GameEvent::BallLost));
+}
+
+#[test]
+fn losing_last_ball_ends_game() {
+    // The ball starts just above the bottom of the arena with nothing to stop it falling out.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.1, 0.05], [0., -1.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .lives(2)
+        .add_block(spare_block())
+        .build();
+
+    for _ in 0..30 {
+        game.tick();
+    }
+
+    // The first ball is lost and a new one is served from the paddle.
+    assert_eq!(game.phase(), Phase::Serve);
+    assert_eq!(game.lives(), 1);
+
+    // Launch it and then get the paddle out of the way, so it is lost too.
+    game.launch();
+    game.paddle_mut().set_velocity([0.7, 0.]);
+    for _ in 0..600 {
+        game.tick();
+    }
+
+    assert_eq!(game.phase(), Phase::GameOver);
+    assert_eq!(game.lives(), 0);
+
+    // Nothing moves once the game is over.
+    let location = game.ball().location();
+    game.tick();
+    assert_eq!(game.phase(), Phase::GameOver);
+    assert_eq!(game.ball().location(), location);
+
+    // The level can still be played again, with the lives it started with.
+    let again = game.to_builder().build();
+    assert_eq!(again.lives(), 2);
+    assert_eq!(again.phase(), Phase::Playing);
+}
+
+#[test]
+fn destroying_last_block_clears_level() {
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.5, 0.4], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.5]))
+        .build();
+
+    for _ in 0..60 {
+        game.tick();
+    }
+
+    assert_eq!(game.phase(), Phase::LevelCleared);
+
+    // The block is worth 10 points, and clearing the level that fast earns a bonus on top.
+    assert!(game.score().points() > 10);
+}
+
+#[test]
+fn level_with_nothing_to_destroy_is_already_cleared() {
+    let steel = Block::new([0.1, 0.05], [0.45, 0.5]).with_kind(BlockKind::Steel);
+    for &blocks in &[&[][..], &[steel][..]] {
+        let mut game = BreakoutBuilder::new()
+            .dt(1. / 120.)
+            .ball(Ball::new(0.01, [0.5, 0.4], [0., 0.5]))
+            .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+            .add_blocks(blocks.iter().cloned())
+            .serve()
+            .build();
+
+        assert_eq!(game.phase(), Phase::LevelCleared);
+        let events = game.tick();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, GameEvent::LevelCleared { .. })));
+    }
+}
+
+#[test]
+fn paused_game_does_not_move() {
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.5, 0.5], [0.3, 0.3]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(spare_block())
+        .build();
+
+    game.pause();
+    let location = game.ball().location();
+    for _ in 0..10 {
+        game.tick();
+        assert_eq!(game.phase(), Phase::Paused);
+    }
+    assert_eq!(game.ball().location(), location);
+
+    game.resume();
+    game.tick();
+    assert_eq!(game.phase(), Phase::Playing);
+    assert!(game.ball().location() != location);
+}
+
+#[test]
+fn served_ball_follows_paddle_until_launched() {
+    let build = || {
+        BreakoutBuilder::new()
+            .dt(1. / 120.)
+            .ball(Ball::new(0.01, [0.5, 0.5], [0., -0.5]))
+            .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+            .serve()
+            .seed(7)
+            .add_block(spare_block())
+            .build()
+    };
+
+    let mut game = build();
+    game.paddle_mut().set_velocity([0.5, 0.]);
+    for _ in 0..60 {
+        game.tick();
+        assert_eq!(game.phase(), Phase::Serve);
+    }
+
+    // The ball sits on top of the middle of the paddle.
+    let [ball_x, ball_y] = game.ball().location();
+    let paddle_x = game.paddle().location()[0];
+    assert!((ball_x - (paddle_x + 0.1)).abs() < 1e-5);
+    assert!((ball_y - 0.13).abs() < 1e-5);
+
+    // A moving paddle sends the ball off in the direction it is moving.
+    game.launch();
+    assert_eq!(game.phase(), Phase::Playing);
+    let [ball_vx, ball_vy] = game.ball().velocity();
+    assert!(ball_vx > 0. && ball_vy > 0.);
+    assert!(((ball_vx * ball_vx + ball_vy * ball_vy).sqrt() - 0.5).abs() < 1e-5);
+
+    // A still paddle uses the seeded spread, so the same seed launches the same way every time.
+    let (mut a, mut b) = (build(), build());
+    a.launch();
+    b.launch();
+    assert_eq!(a.ball().velocity(), b.ball().velocity());
+}
+
+#[test]
+fn tick_reports_events() {
+    // Heads up into a block, then back down onto the middle of the paddle.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.5, 0.4], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.5]))
+        .add_block(Block::new([0.1, 0.05], [0.05, 0.9]))
+        .build();
+
+    let mut events = vec![];
+    for _ in 0..180 {
+        events.extend(game.tick());
+    }
+
+    let mut destroyed = Block::new([0.1, 0.05], [0.45, 0.5]);
+    destroyed.hit(0.5);
+
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::BallHitBlock {
+                index: 0,
+                face: Face::Bottom,
+            },
+            GameEvent::BlockDestroyed {
+                index: 0,
+                block: destroyed,
+                points: 10,
+            },
+            GameEvent::BallHitPaddle { offset: 0. },
+        ]
+    );
+
+    // Nothing else is in the way, so the ball goes straight to the top wall.
+    let events = (0..240).flat_map(|_| game.tick()).collect::<Vec<_>>();
+    assert_eq!(events, vec![GameEvent::BallHitWall(Wall::Top)]);
+}
+
+#[test]
+fn tough_blocks_take_several_hits() {
+    // The ball bounces back and forth between the paddle and a block above it.
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.5, 0.4], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.5]).with_hit_points(2))
+        .add_block(Block::new([0.1, 0.05], [0.05, 0.9]).with_kind(BlockKind::Steel))
+        .build();
+
+    let mut events = vec![];
+    for _ in 0..120 {
+        events.extend(game.tick());
+    }
+
+    assert!(events.contains(&GameEvent::BlockDamaged {
+        index: 0,
+        hit_points: 1,
+    }));
+    assert_eq!(game.blocks()[0].unwrap().hit_points(), 1);
+
+    for _ in 0..240 {
+        game.tick();
+    }
+    assert!(game.blocks()[0].is_none());
+
+    // Steel never breaks, so once the normal block is gone the level is over.
+    assert!(game.blocks()[1].is_some());
+    assert_eq!(game.phase(), Phase::LevelCleared);
+}
+
+#[test]
+fn armored_blocks_need_a_fast_ball() {
+    let build = |speed| {
+        BreakoutBuilder::new()
+            .dt(1. / 120.)
+            .ball(Ball::new(0.01, [0.5, 0.4], [0., speed]))
+            .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+            .add_block(
+                Block::new([0.1, 0.05], [0.45, 0.5])
+                    .with_kind(BlockKind::Armored { min_speed: 1. }),
+            )
+            .build()
+    };
+
+    let mut slow = build(0.5);
+    let mut fast = build(2.);
+    for _ in 0..60 {
+        slow.tick();
+        fast.tick();
+    }
+
+    assert!(slow.blocks()[0].is_some());
+    assert!(fast.blocks()[0].is_none());
+    assert_eq!(fast.phase(), Phase::LevelCleared);
+}
+
+#[test]
+fn explosions_chain_within_a_tick() {
+    // A row of blocks along the top of the arena. The ball sets off the explosive block on the
+    // left end, which should take out its neighbour and set off the next explosive block, and so
+    // on down the row. The block on the far right is out of range of every explosion.
+    let explosive = BlockKind::Explosive { radius: 0.08 };
+    let mut game = BreakoutBuilder::new()
+        .dt(1. / 120.)
+        .ball(Ball::new(0.01, [0.15, 0.7], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.8]).with_kind(explosive))
+        .add_block(Block::new([0.1, 0.05], [0.2, 0.8]))
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.85]).with_kind(explosive))
+        .add_block(Block::new([0.1, 0.05], [0.2, 0.85]).with_hit_points(2))
+        .add_block(Block::new([0.1, 0.05], [0.8, 0.8]))
+        .build();
+
+    let mut events = vec![];
+    while events.is_empty() {
+        events = game.tick();
+    }
+
+    let destroyed = events
+        .iter()
+        .filter_map(|event| match *event {
+            GameEvent::BlockDestroyed { index, .. } => Some(index),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let explosions = events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::Explosion { .. }))
+        .count();
+
+    // Block 3 is caught by both explosions, so it loses both of its hit points.
+    assert_eq!(destroyed, vec![0, 1, 2, 3]);
+    assert_eq!(explosions, 2);
+    assert!(game.blocks()[4].is_some());
+}
+
+#[test]
+fn sticky_paddle_holds_ball_until_launched() {
+    // The ball lands a quarter of the way in from the right end of the paddle.
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.55, 0.2], [0., -0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .sticky_paddle()
+        .add_block(spare_block())
+        .build();
+
+    let mut events = vec![];
+    for _ in 0..30 {
+        events.extend(game.tick());
+    }
+    assert!(events.contains(&GameEvent::BallCaught));
+    assert!(game.ball_is_held(0));
+    assert_eq!(game.ball().velocity(), [0., 0.]);
+
+    // The ball moves along with the paddle.
+    game.paddle_mut().set_velocity([-0.5, 0.]);
+    for _ in 0..10 {
+        game.tick();
+    }
+    let [ball_x, ball_y] = game.ball().location();
+    assert!((ball_x - (game.paddle().left() + 0.15)).abs() < 0.0001);
+    assert!((ball_y - 0.13).abs() < 0.0001);
+
+    // It goes off the way it would have bounced, with the usual speed up.
+    game.launch();
+    let [ball_vx, ball_vy] = game.ball().velocity();
+    let speed = 0.5 * 1.05;
+    assert!((ball_vx - 0.5 * 0.8 * speed).abs() < 0.0001);
+    assert!((ball_vx * ball_vx + ball_vy * ball_vy - speed * speed).abs() < 0.0001);
+    assert!(!game.ball_is_held(0));
+}
+
+#[test]
+fn sticky_paddle_lets_go_after_a_while() {
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.2], [0., -0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .sticky_paddle()
+        .add_block(spare_block())
+        .build();
+
+    for _ in 0..30 {
+        game.tick();
+    }
+    assert!(game.ball_is_held(0));
+
+    let mut events = vec![];
+    for _ in 0..200 {
+        events.extend(game.tick());
+    }
+    assert!(events.contains(&GameEvent::BallLaunched));
+    assert!(game.ball().velocity()[1] > 0.);
+}
+
+#[test]
+fn moving_paddle_puts_spin_on_ball() {
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.2], [0., -0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(spare_block())
+        .build();
+    game.paddle_mut().set_velocity([0.2, 0.]);
+
+    let mut events = vec![];
+    while !events
+        .iter()
+        .any(|event| matches!(event, GameEvent::BallHitPaddle { .. }))
+    {
+        events = game.tick();
+    }
+    game.paddle_mut().set_velocity([0., 0.]);
+
+    // The paddle was moving right, so the ball curves to the right.
+    let spin = game.ball().spin();
+    assert!(spin < 0.);
+
+    let [vx, vy] = game.ball().velocity();
+    for _ in 0..20 {
+        game.tick();
+    }
+    let [curved_vx, curved_vy] = game.ball().velocity();
+    assert!(curved_vx / curved_vy > vx / vy);
+
+    // The spin wears off as it goes.
+    assert!(game.ball().spin() > spin && game.ball().spin() < 0.);
+}
+
+#[test]
+fn spinning_ball_kicks_off_walls() {
+    let bounce = |spin: f32| {
+        let mut ball = Ball::new(0.01, [0.95, 0.5], [0.5, 0.5]);
+        ball.set_spin(spin);
+
+        let mut game = BreakoutBuilder::new()
+            .dt(0.01)
+            .ball(ball)
+            .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+            .add_block(spare_block())
+            .build();
+
+        while !game.tick().contains(&GameEvent::BallHitWall(Wall::Right)) {}
+        game.ball().velocity()
+    };
+
+    let [plain_vx, plain_vy] = bounce(0.);
+    let [spun_vx, spun_vy] = bounce(1.);
+
+    // Spinning counter-clockwise turns the ball further around, flattening out its path.
+    assert!(plain_vx < 0. && spun_vx < 0.);
+    assert!(spun_vy / spun_vx > plain_vy / plain_vx);
+}
+
+#[test]
+fn physics_config_tunes_paddle_bounces() {
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.59, 0.2], [0., -1.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .physics(PhysicsConfig {
+            paddle_hit_speedup: 2.,
+            max_horizontal_fraction: 0.5,
+            max_ball_speed: 2.5,
+            ..PhysicsConfig::new()
+        })
+        .add_block(spare_block())
+        .build();
+
+    while !game
+        .tick()
+        .iter()
+        .any(|event| matches!(event, GameEvent::BallHitPaddle { .. }))
+    {}
+
+    // Doubling the speed would go past the limit. Hitting 90% of the way to the end of the
+    // paddle sends the ball off at 90% of the largest sideways fraction allowed.
+    let [ball_vx, ball_vy] = game.ball().velocity();
+    assert!((ball_vx - 0.9 * 0.5 * 2.5).abs() < 0.0001, "{}", ball_vx);
+    assert!((ball_vx * ball_vx + ball_vy * ball_vy - 2.5 * 2.5).abs() < 0.0001);
+}
+
+#[test]
+fn flat_bounces_get_some_vertical_speed() {
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.9, 0.5], [0.5, 0.]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .physics(PhysicsConfig {
+            min_vertical_speed: 0.2,
+            wall_restitution: 0.5,
+            ..PhysicsConfig::new()
+        })
+        .add_block(spare_block())
+        .build();
+
+    while !game.tick().contains(&GameEvent::BallHitWall(Wall::Right)) {}
+
+    // The wall took half of the ball's speed, and what's left is turned to go up a little.
+    let [ball_vx, ball_vy] = game.ball().velocity();
+    assert!((ball_vy - 0.2).abs() < 0.0001, "{}", ball_vy);
+    assert!((ball_vx * ball_vx + ball_vy * ball_vy - 0.25 * 0.25).abs() < 0.0001);
+    assert!(ball_vx < 0.);
+}
+
+#[test]
+fn steering_uses_paddle_speed() {
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.5], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .physics(PhysicsConfig {
+            paddle_speed: 0.4,
+            ..PhysicsConfig::new()
+        })
+        .add_block(spare_block())
+        .build();
+
+    game.steer_paddle(-0.5);
+    assert_eq!(game.paddle().velocity(), [-0.2, 0.]);
+
+    game.steer_paddle(3.);
+    assert_eq!(game.paddle().velocity(), [0.4, 0.]);
 }
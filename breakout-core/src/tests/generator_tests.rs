@@ -0,0 +1,116 @@
+use super::super::{BlockKind, DensityCurve, Generator, Rectangle, Symmetry};
+
+#[test]
+fn same_seed_makes_the_same_level() {
+    let generator = Generator::new();
+    let level = generator.generate(7).dt(0.01).build();
+    assert_eq!(
+        level.blocks(),
+        generator.generate(7).dt(0.01).build().blocks()
+    );
+    assert_ne!(
+        level.blocks(),
+        generator.generate(8).dt(0.01).build().blocks()
+    );
+}
+
+#[test]
+fn endless_rounds_dont_share_settings() {
+    // Round and seed aren't just xored together, so these would otherwise match.
+    for seed in 0..10 {
+        let first = Generator::endless(1, seed);
+        let second = Generator::endless(0, seed ^ 1);
+        assert_ne!(first.density, second.density);
+    }
+}
+
+#[test]
+fn generated_levels_are_playable() {
+    for round in 0..20 {
+        for seed in 0..10 {
+            let generator = Generator::endless(round, seed);
+            let game = generator.generate(seed).dt(0.01).try_build().unwrap();
+            assert!(game
+                .blocks()
+                .iter()
+                .flatten()
+                .any(|block| block.is_destructible()));
+        }
+    }
+
+    let empty = Generator {
+        density: 0.,
+        ..Generator::new()
+    };
+    assert!(empty.generate(1).dt(0.01).try_build().is_ok());
+}
+
+#[test]
+fn mirrored_levels_are_symmetric() {
+    let generator = Generator {
+        columns: 9,
+        symmetry: Symmetry::Mirror,
+        ..Generator::new()
+    };
+
+    for seed in 0..10 {
+        let game = generator.generate(seed).dt(0.01).build();
+        let blocks: Vec<_> = game.blocks().iter().flatten().collect();
+        for block in &blocks {
+            let mirrored = 1. - block.right();
+            assert!(blocks.iter().any(|other| {
+                (other.left() - mirrored).abs() < 0.0001
+                    && other.bottom() == block.bottom()
+                    && other.kind() == block.kind()
+            }));
+        }
+    }
+}
+
+#[test]
+fn density_curve_shapes_the_rows() {
+    let count = |curve, top: bool| {
+        let generator = Generator {
+            density_curve: curve,
+            symmetry: Symmetry::None,
+            gap_chance: 0.,
+            ..Generator::new()
+        };
+        (0..20)
+            .map(|seed| {
+                let game = generator.generate(seed).dt(0.01).build();
+                game.blocks()
+                    .iter()
+                    .flatten()
+                    .filter(|block| (block.bottom() > 0.75) == top)
+                    .count()
+            })
+            .sum::<usize>()
+    };
+
+    assert!(count(DensityCurve::Top, true) > count(DensityCurve::Top, false));
+    assert!(count(DensityCurve::Bottom, true) < count(DensityCurve::Bottom, false));
+}
+
+#[test]
+fn harder_levels_have_tougher_blocks() {
+    let tough = |difficulty| {
+        let generator = Generator {
+            difficulty,
+            ..Generator::new()
+        };
+        (0..20)
+            .map(|seed| {
+                let game = generator.generate(seed).dt(0.01).build();
+                game.blocks()
+                    .iter()
+                    .flatten()
+                    .filter(|block| block.kind() != BlockKind::Normal || block.hit_points() > 1)
+                    .count()
+            })
+            .sum::<usize>()
+    };
+
+    assert!(tough(0.) < tough(1.));
+    assert_ne!(Generator::daily_seed(100), Generator::daily_seed(101));
+}
@@ -0,0 +1,143 @@
+use super::super::{
+    Block, BlockKind, Breakout, BreakoutBuilder, Circle, GameObject, Level, LevelInfo, Paddle,
+    Phase, PowerUp,
+};
+
+const LEVEL: &str = "
+// A small test level.
+name: Test Level
+author: Somebody
+par: 30
+lives: 5
+ball: 0.01 0.5 0.3 0 0.5
+paddle: 0.2 0.02 0.4 0.1
+serve: true
+physics: paddle_speed=0.5 wall_restitution=0.9
+drops: 0.5 laser=2 extra_life=1
+cell: 0.1 0.05
+origin: 0.1 0.9
+legend: A armored 2 1.5
+legend: * explosive 1 0.12
+grid:
+#@A
+ *#
+";
+
+fn close(a: [f32; 2], b: [f32; 2]) -> bool {
+    (a[0] - b[0]).abs() < 0.0001 && (a[1] - b[1]).abs() < 0.0001
+}
+
+#[test]
+fn parses_header_and_grid() {
+    let level = Level::parse(LEVEL).unwrap();
+    assert_eq!(level.info().name, "Test Level");
+    assert_eq!(level.info().author, "Somebody");
+    assert_eq!(level.par_time(), Some(30.));
+
+    let game = level.builder().dt(0.01).build();
+    assert_eq!(game.lives(), 5);
+    assert_eq!(game.phase(), Phase::Serve);
+    assert_eq!(game.score().par_time(), 30.);
+    assert_eq!(game.physics().paddle_speed, 0.5);
+    assert_eq!(game.physics().wall_restitution, 0.9);
+
+    let blocks: Vec<Block> = game.blocks().iter().flatten().cloned().collect();
+    let kinds: Vec<BlockKind> = blocks.iter().map(|block| block.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            BlockKind::Normal,
+            BlockKind::Steel,
+            BlockKind::Armored { min_speed: 1.5 },
+            BlockKind::Explosive { radius: 0.12 },
+            BlockKind::Normal,
+        ]
+    );
+    assert_eq!(blocks[2].hit_points(), 2);
+
+    // Rows go down from the top of the grid, and spaces are left empty.
+    assert!(close(blocks[0].location(), [0.1, 0.85]));
+    assert!(close(blocks[2].location(), [0.3, 0.85]));
+    assert!(close(blocks[3].location(), [0.2, 0.8]));
+}
+
+#[test]
+fn errors_say_which_line() {
+    let error = Level::parse("name: Oops\nball: 0.01 0.5 0.3 0\n").unwrap_err();
+    assert_eq!(error.line(), 2);
+
+    let error = Level::parse("name: Oops\n\nshape: round\n").unwrap_err();
+    assert_eq!(error.line(), 3);
+    assert_eq!(error.message(), "unknown key `shape`");
+
+    let error = Level::parse("legend: A armored 2\n").unwrap_err();
+    assert_eq!(error.line(), 1);
+
+    let error = Level::parse("drops: 0.5 lasers=1\n").unwrap_err();
+    assert_eq!(error.message(), "unknown power-up `lasers`");
+}
+
+#[test]
+fn written_level_reads_back_the_same() {
+    let game = Level::parse(LEVEL).unwrap().builder().dt(0.01).build();
+    let info = LevelInfo {
+        name: "Test Level".to_owned(),
+        author: "Somebody".to_owned(),
+    };
+
+    let text = Level::from_game(info.clone(), &game).to_string();
+    let level = Level::parse(&text).unwrap();
+    assert_eq!(level.info(), &info);
+
+    let copy = level.builder().dt(0.01).build();
+    let blocks: Vec<Block> = game.blocks().iter().flatten().cloned().collect();
+    let copied: Vec<Block> = copy.blocks().iter().flatten().cloned().collect();
+    assert_eq!(blocks.len(), copied.len());
+    for (block, copied) in blocks.iter().zip(&copied) {
+        assert_eq!(block.kind(), copied.kind());
+        assert_eq!(block.max_hit_points(), copied.max_hit_points());
+        assert!(close(block.location(), copied.location()));
+    }
+
+    assert_eq!(copy.ball().radius(), game.ball().radius());
+    assert_eq!(copy.paddle().location(), game.paddle().location());
+    assert_eq!(copy.physics(), game.physics());
+    assert_eq!(copy.lives(), game.lives());
+    assert_eq!(copy.score().par_time(), 30.);
+
+    let drops = level.builder().drop_table;
+    assert_eq!(drops.chance(), 0.5);
+    assert_eq!(
+        drops.weights(),
+        &[(PowerUp::Laser, 2), (PowerUp::ExtraLife, 1)][..]
+    );
+}
+
+#[test]
+fn blocks_off_the_grid_are_written_one_at_a_time() {
+    let game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(::Ball::new(0.01, [0.5, 0.3], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .add_block(Block::new([0.1, 0.05], [0.1, 0.8]))
+        .add_block(Block::new([0.2, 0.05], [0.5, 0.73]).with_hit_points(3))
+        .build();
+
+    let text = Level::from_game(LevelInfo::default(), &game).to_string();
+    assert!(!text.contains("grid:"));
+
+    let copy = Level::parse(&text).unwrap().builder().dt(0.01).build();
+    assert_eq!(copy.blocks(), game.blocks());
+}
+
+#[test]
+fn built_in_level_survives_a_round_trip() {
+    let game = Breakout::level_1(0.01);
+    let text = Level::from_game(LevelInfo::default(), &game).to_string();
+    assert!(text.contains("grid:\n#.#.#.#\n"), "{}", text);
+    assert!(!text.contains("par:"), "{}", text);
+
+    let copy = Level::parse(&text).unwrap().builder().dt(0.01).build();
+    assert_eq!(copy.blocks().len(), 4);
+    assert_eq!(copy.phase(), Phase::Serve);
+}
@@ -1 +1,11 @@
+mod builder_tests;
+mod campaign_tests;
+mod controller_tests;
+mod environment_tests;
+mod frame_tests;
 mod game_tests;
+mod generator_tests;
+mod level_tests;
+mod powerup_tests;
+mod replay_tests;
+mod score_tests;
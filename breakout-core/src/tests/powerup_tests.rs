@@ -0,0 +1,137 @@
+use super::super::{
+    Ball, Block, Breakout, BreakoutBuilder, DropTable, GameEvent, GameObject, Paddle, PowerUp,
+    Rectangle,
+};
+
+/// A game where the ball is about to destroy a block that always drops `power_up` right above
+/// the paddle.
+fn dropping(power_up: PowerUp) -> Breakout {
+    BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.26], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .drop_table(DropTable::new(1.).with(power_up, 1))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.3]))
+        // Keeps the level going once the first block is gone.
+        .add_block(Block::new([0.1, 0.05], [0.05, 0.9]))
+        .build()
+}
+
+/// Ticks the game until `event` happens, and panics if it doesn't within `max_ticks`.
+fn tick_until(game: &mut Breakout, event: GameEvent, max_ticks: usize) {
+    for _ in 0..max_ticks {
+        if game.tick().contains(&event) {
+            return;
+        }
+    }
+
+    panic!("{:?} didn't happen within {} ticks", event, max_ticks);
+}
+
+#[test]
+fn destroyed_block_drops_capsule() {
+    let mut game = dropping(PowerUp::SlowBall);
+
+    tick_until(&mut game, GameEvent::CapsuleDropped(PowerUp::SlowBall), 100);
+    assert_eq!(game.capsules().len(), 1);
+    assert_eq!(game.capsules()[0].power_up(), PowerUp::SlowBall);
+
+    tick_until(&mut game, GameEvent::PowerUpCaught(PowerUp::SlowBall), 200);
+    assert!(game.capsules().is_empty());
+}
+
+#[test]
+fn wide_paddle_wears_off() {
+    let mut game = dropping(PowerUp::WidePaddle);
+
+    tick_until(
+        &mut game,
+        GameEvent::PowerUpCaught(PowerUp::WidePaddle),
+        300,
+    );
+    assert!((game.paddle().dimensions()[0] - 0.3).abs() < 0.0001);
+    assert!((game.paddle().left() - 0.35).abs() < 0.0001);
+
+    tick_until(
+        &mut game,
+        GameEvent::PowerUpExpired(PowerUp::WidePaddle),
+        1600,
+    );
+    assert!((game.paddle().dimensions()[0] - 0.2).abs() < 0.0001);
+    assert!(game.effects().is_empty());
+}
+
+#[test]
+fn extra_life_adds_a_life() {
+    let mut game = dropping(PowerUp::ExtraLife);
+
+    tick_until(&mut game, GameEvent::PowerUpCaught(PowerUp::ExtraLife), 300);
+    assert_eq!(game.lives(), 4);
+    assert!(game.effects().is_empty());
+}
+
+#[test]
+fn slow_ball_slows_the_ball() {
+    let mut game = dropping(PowerUp::SlowBall);
+
+    tick_until(&mut game, GameEvent::PowerUpCaught(PowerUp::SlowBall), 300);
+    let [_, ball_vy] = game.ball().velocity();
+
+    // The ball has bounced off of the paddle once since it was at 0.5 units per second.
+    assert!(
+        (ball_vy.abs() - 0.5 * 1.05 * 0.6).abs() < 0.0001,
+        "{}",
+        ball_vy
+    );
+}
+
+#[test]
+fn multi_ball_splits_the_ball() {
+    let mut game = dropping(PowerUp::MultiBall);
+
+    tick_until(&mut game, GameEvent::PowerUpCaught(PowerUp::MultiBall), 300);
+    assert_eq!(game.balls().len(), 3);
+
+    // The new balls head off in different directions from the same place.
+    let [a, b, c] = [game.balls()[0], game.balls()[1], game.balls()[2]];
+    assert_eq!(a.location(), b.location());
+    assert_eq!(a.location(), c.location());
+    assert!(a.velocity() != b.velocity() && b.velocity() != c.velocity());
+}
+
+#[test]
+fn laser_damages_blocks_above_paddle() {
+    let mut game = BreakoutBuilder::new()
+        .dt(0.01)
+        .ball(Ball::new(0.01, [0.5, 0.26], [0., 0.5]))
+        .paddle(Paddle::new([0.2, 0.02], [0.4, 0.1]))
+        .drop_table(DropTable::new(1.).with(PowerUp::Laser, 1))
+        .add_block(Block::new([0.1, 0.05], [0.45, 0.3]))
+        // Right above the left end of the paddle, out of the ball's way.
+        .add_block(Block::new([0.1, 0.05], [0.35, 0.6]))
+        .add_block(Block::new([0.1, 0.05], [0.05, 0.9]))
+        .build();
+
+    // Nothing happens without the laser.
+    game.fire();
+    assert!(game.projectiles().is_empty());
+
+    tick_until(&mut game, GameEvent::PowerUpCaught(PowerUp::Laser), 300);
+    assert!(game.laser_active());
+
+    game.fire();
+    assert_eq!(game.projectiles().len(), 2);
+
+    // The laser needs to cool down between shots.
+    game.fire();
+    assert_eq!(game.projectiles().len(), 2);
+
+    tick_until(&mut game, GameEvent::LaserHitBlock { index: 1 }, 100);
+    assert!(game.blocks()[1].is_none());
+
+    // The other projectile misses everything and flies out of the top of the arena.
+    for _ in 0..100 {
+        game.tick();
+    }
+    assert!(game.projectiles().is_empty());
+}
@@ -0,0 +1,103 @@
+use super::super::{
+    Ball, Block, Breakout, BreakoutBuilder, Command, GameObject, Paddle, Phase, Replay,
+};
+
+/// Plays `game` for a while, chasing the ball and pressing buttons now and then, and records
+/// what was done.
+fn record(mut game: Breakout, ticks: usize) -> (Breakout, Replay) {
+    let mut replay = Replay::new(&game);
+    for tick in 0..ticks {
+        // Hitting the ball with different parts of the paddle sends it all over the place.
+        let aim = [0.03, 0.07, 0.12, 0.05][tick / 2000 % 4];
+        let offset = game.ball().location()[0] - game.paddle().location()[0] - aim;
+        let command = Command {
+            steer: (offset * 20.).clamp(-1., 1.),
+            launch: tick % 500 == 0,
+            fire: tick % 97 == 0,
+            pause: tick % 3000 == 1500 || tick % 3000 == 1600,
+        };
+
+        game.apply(command);
+        replay.record(command);
+        game.tick();
+    }
+    (game, replay)
+}
+
+#[test]
+fn replay_plays_the_game_back_exactly() {
+    let (game, replay) = record(Breakout::level_1(1. / 960.), 30_000);
+    assert_eq!(replay.commands().len(), 30_000);
+    assert!(game.score().points() > 0);
+
+    let copy = Replay::parse(&replay.to_string()).unwrap();
+    assert_eq!(copy.commands(), replay.commands());
+    assert_eq!(format!("{:?}", copy.play()), format!("{:?}", game));
+}
+
+#[test]
+fn spinning_ball_plays_back_exactly() {
+    let mut ball = Ball::new(0.015, [0.5, 0.4], [0.1, 0.5]);
+    ball.set_spin(2.5);
+    let game = BreakoutBuilder::new()
+        .dt(1. / 960.)
+        .ball(ball)
+        .paddle(Paddle::new([0.15, 0.02], [0.425, 0.065]))
+        .add_blocks((0..4_i32).map(|i| Block::new([0.1, 0.05], [0.2 * i as f32 + 0.15, 0.8])))
+        .build();
+    let (game, replay) = record(game, 5000);
+
+    let copy = Replay::parse(&replay.to_string()).unwrap();
+    assert_eq!(copy.game().ball().spin(), 2.5);
+    assert_eq!(format!("{:?}", copy.play()), format!("{:?}", game));
+}
+
+#[test]
+fn repeated_commands_are_written_once() {
+    let mut replay = Replay::new(&Breakout::level_1(0.01));
+    for _ in 0..100 {
+        replay.record(Command::default());
+    }
+    replay.record(Command {
+        launch: true,
+        ..Command::steer(0.5)
+    });
+    for _ in 0..20 {
+        replay.record(Command::steer(-1.));
+    }
+
+    let text = replay.to_string();
+    assert!(
+        text.ends_with("commands:\n100 0\n1 0.5 launch\n20 -1\n"),
+        "{}",
+        text
+    );
+
+    let game = Replay::parse(&text).unwrap().play();
+    assert_eq!(game.phase(), Phase::Playing);
+}
+
+#[test]
+fn bad_commands_say_which_line() {
+    let text = Replay::new(&Breakout::level_1(0.01)).to_string() + "5 0\n1 0 jump\n";
+    let error = Replay::parse(&text).unwrap_err();
+    assert_eq!(error.line(), text.lines().count());
+    assert_eq!(error.message(), "unknown button `jump`");
+
+    assert!(Replay::parse("points: 0\nlevel:\ncommands:\n").is_err());
+}
+
+#[test]
+fn replays_that_cant_be_played_are_errors() {
+    let text = Replay::new(&Breakout::level_1(0.01)).to_string();
+
+    // The game is checked when the replay is read, not when it is played.
+    let error = Replay::parse(&text.replace("dt: 0.01", "dt: 0")).unwrap_err();
+    assert_eq!(error.line(), 4);
+
+    let error = Replay::parse(&(text.clone() + "99999999999 0\n")).unwrap_err();
+    assert_eq!(error.line(), text.lines().count() + 1);
+
+    let error = Replay::parse(&(text + "6000000 0\n6000000 0\n")).unwrap_err();
+    assert!(error.message().contains("ticks long"));
+}
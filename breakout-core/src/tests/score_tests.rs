@@ -0,0 +1,39 @@
+use super::super::Score;
+
+#[test]
+fn combo_multiplies_block_points() {
+    let mut score = Score::new();
+
+    assert_eq!(score.block_destroyed(10), 10);
+    assert_eq!(score.block_destroyed(10), 20);
+    assert_eq!(score.block_destroyed(10), 30);
+    assert_eq!(score.points(), 60);
+
+    // Touching the paddle starts the combo over.
+    score.paddle_hit();
+    assert_eq!(score.multiplier(), 1);
+    assert_eq!(score.block_destroyed(10), 10);
+    assert_eq!(score.points(), 70);
+}
+
+#[test]
+fn quick_clear_earns_bonus() {
+    let mut fast = Score::new();
+    fast.tick(10.);
+    assert_eq!(fast.level_cleared(), 2500);
+
+    let mut slow = Score::new();
+    slow.tick(90.);
+    assert_eq!(slow.level_cleared(), 0);
+}
+
+#[test]
+fn points_stop_at_the_most_a_score_can_hold() {
+    let mut score = Score::with_par_time(1e8);
+    assert_eq!(score.block_destroyed(u32::MAX), u32::MAX);
+    assert_eq!(score.block_destroyed(10), 20);
+    assert_eq!(score.points(), u32::MAX);
+
+    assert_eq!(score.level_cleared(), u32::MAX);
+    assert_eq!(score.points(), u32::MAX);
+}
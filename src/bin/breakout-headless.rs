@@ -0,0 +1,351 @@
+//! Runs the game without a window, for CI and balancing scripts. The game's state is printed as
+//! one JSON object per line: every `--every` ticks if asked, and always once at the end.
+
+extern crate breakout_core;
+
+use breakout_core::prelude::*;
+use std::env;
+use std::fs;
+use std::process;
+
+const USAGE: &str = "\
+usage: breakout-headless [options]
+
+    --level PATH     play a level file instead of the built-in level
+    --replay PATH    play a replay back, with its own level and input, to its end
+    --script PATH    input as replay command lines, e.g. `1 0 launch` then `500 -1`
+    --ai NAME        let `tracker` or `predictive` play once the script runs out
+    --seed N         use a different seed for the level
+    --dt SECONDS     seconds per tick, 1/960 by default
+    --ticks N        stop after N ticks, even if the game isn't over
+    --every N        print the state every N ticks as well as at the end";
+
+const DEFAULT_DT: f32 = 1. / 960.;
+
+/// How long to run for when the game never ends, in seconds of game time.
+const DEFAULT_TIME_LIMIT: f32 = 600.;
+
+struct Options {
+    level: Option<String>,
+    replay: Option<String>,
+    script: Option<String>,
+    ai: Option<Box<dyn PaddleController>>,
+    seed: Option<u64>,
+    dt: Option<f32>,
+    ticks: Option<u64>,
+    every: Option<u64>,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        level: None,
+        replay: None,
+        script: None,
+        ai: None,
+        seed: None,
+        dt: None,
+        ticks: None,
+        every: None,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("`{}` needs a value", arg))
+        };
+        let number_error = |value: &str| format!("`{}` is not a valid value for `{}`", value, arg);
+
+        match &arg[..] {
+            "--level" => options.level = Some(value()?),
+            "--replay" => options.replay = Some(value()?),
+            "--script" => options.script = Some(value()?),
+            "--ai" => {
+                options.ai = match &value()?[..] {
+                    "tracker" => Some(Box::new(TrackingController::new())),
+                    "predictive" => Some(Box::new(PredictiveController::new())),
+                    name => return Err(format!("unknown AI `{}`", name)),
+                };
+            }
+            "--seed" => {
+                let value = value()?;
+                options.seed = Some(value.parse().map_err(|_| number_error(&value))?);
+            }
+            "--dt" => {
+                let value = value()?;
+                options.dt = Some(value.parse().map_err(|_| number_error(&value))?);
+            }
+            "--ticks" => {
+                let value = value()?;
+                options.ticks = Some(value.parse().map_err(|_| number_error(&value))?);
+            }
+            "--every" => {
+                let value = value()?;
+                options.every = Some(value.parse().map_err(|_| number_error(&value))?);
+            }
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("unknown option `{}`", arg)),
+        }
+    }
+
+    if options.replay.is_some()
+        && (options.level.is_some()
+            || options.script.is_some()
+            || options.ai.is_some()
+            || options.seed.is_some()
+            || options.dt.is_some())
+    {
+        return Err("a replay already has its level, input, seed and dt".to_owned());
+    }
+    if options.every == Some(0) {
+        return Err("`--every` has to be at least 1".to_owned());
+    }
+
+    Ok(options)
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("{}: {}", path, error);
+        process::exit(1);
+    })
+}
+
+/// The game to run and the command for each tick. Ticks past the end of a script get no input,
+/// unless an AI is playing. A replay never runs past the end of its commands.
+fn load(options: &Options) -> (Breakout, Vec<Command>, String) {
+    if let Some(ref path) = options.replay {
+        // Reading the replay checks that its game can be built, so `game` can't fail.
+        let replay = Replay::parse(&read(path)).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path, error);
+            process::exit(1);
+        });
+        return (replay.game(), replay.commands().to_vec(), String::new());
+    }
+
+    let dt = options.dt.unwrap_or(DEFAULT_DT);
+
+    let level = match options.level {
+        Some(ref path) => Level::parse(&read(path)).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path, error);
+            process::exit(1);
+        }),
+        None => {
+            let info = LevelInfo {
+                name: "Level 1".to_owned(),
+                ..LevelInfo::default()
+            };
+            Level::from_game(info, &Breakout::level_1(dt))
+        }
+    };
+
+    let mut builder = level.builder().dt(dt);
+    if let Some(seed) = options.seed {
+        builder = builder.seed(seed);
+    }
+    let game = builder.try_build().unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+
+    let commands = match options.script {
+        Some(ref path) => Replay::parse_commands(&read(path)).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path, error);
+            process::exit(1);
+        }),
+        None => vec![],
+    };
+
+    (game, commands, level.info().name.clone())
+}
+
+fn main() {
+    let mut options = parse_options().unwrap_or_else(|error| fail(&error));
+    let (mut game, commands, name) = load(&options);
+
+    let limit = if options.replay.is_some() {
+        // Stopping where the recording did ends on the same state as `Replay::play`.
+        let recorded = commands.len() as u64;
+        options.ticks.map_or(recorded, |ticks| ticks.min(recorded))
+    } else {
+        options
+            .ticks
+            .unwrap_or_else(|| (DEFAULT_TIME_LIMIT / game.dt()) as u64)
+    };
+
+    let mut events = EventCounts::new();
+    let mut tick = 0;
+    while tick < limit && !is_over(&game) {
+        let command = match (commands.get(tick as usize), options.ai.as_mut()) {
+            (Some(&command), _) => command,
+            (None, Some(ai)) => ai.command(&game),
+            (None, None) => Command::default(),
+        };
+        game.apply(command);
+        for event in game.tick() {
+            events.add(&event);
+        }
+        tick += 1;
+
+        if let Some(every) = options.every {
+            if tick % every == 0 && tick < limit && !is_over(&game) {
+                println!("{}", state_json(&game, &name, tick, &events, false));
+                events = EventCounts::new();
+            }
+        }
+    }
+
+    println!("{}", state_json(&game, &name, tick, &events, true));
+}
+
+fn is_over(game: &Breakout) -> bool {
+    game.phase() == Phase::GameOver || game.phase() == Phase::LevelCleared
+}
+
+/// How many of each kind of event have happened since the state was last printed.
+struct EventCounts {
+    counts: Vec<(&'static str, u32)>,
+}
+
+impl EventCounts {
+    fn new() -> Self {
+        Self { counts: vec![] }
+    }
+
+    fn add(&mut self, event: &GameEvent) {
+        let name = event_name(event);
+        match self.counts.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => *count += 1,
+            None => self.counts.push((name, 1)),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let fields: Vec<String> = self
+            .counts
+            .iter()
+            .map(|&(name, count)| format!("\"{}\":{}", name, count))
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+fn event_name(event: &GameEvent) -> &'static str {
+    match *event {
+        GameEvent::BallLaunched => "ball_launched",
+        GameEvent::BallHitPaddle { .. } => "ball_hit_paddle",
+        GameEvent::BallCaught => "ball_caught",
+        GameEvent::BallHitWall(_) => "ball_hit_wall",
+        GameEvent::BallHitBlock { .. } => "ball_hit_block",
+        GameEvent::BlockDamaged { .. } => "block_damaged",
+        GameEvent::BlockDestroyed { .. } => "block_destroyed",
+        GameEvent::Explosion { .. } => "explosion",
+        GameEvent::CapsuleDropped(_) => "capsule_dropped",
+        GameEvent::PowerUpCaught(_) => "power_up_caught",
+        GameEvent::PowerUpExpired(_) => "power_up_expired",
+        GameEvent::LaserFired => "laser_fired",
+        GameEvent::LaserHitBlock { .. } => "laser_hit_block",
+        GameEvent::ExtraBallLost => "extra_ball_lost",
+        GameEvent::BallLost => "ball_lost",
+        GameEvent::GameOver => "game_over",
+        GameEvent::LevelCleared { .. } => "level_cleared",
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// JSON has no way to write infinities or NaN, so they are written as `null`.
+fn json_number(number: f32) -> String {
+    if number.is_finite() {
+        number.to_string()
+    } else {
+        "null".to_owned()
+    }
+}
+
+fn json_vec2(v: vec2) -> String {
+    format!("[{},{}]", json_number(v[0]), json_number(v[1]))
+}
+
+fn state_json(game: &Breakout, name: &str, tick: u64, events: &EventCounts, last: bool) -> String {
+    let paddle = game.paddle();
+    let balls: Vec<String> = game
+        .balls()
+        .iter()
+        .enumerate()
+        .map(|(i, ball)| {
+            format!(
+                "{{\"location\":{},\"velocity\":{},\"radius\":{},\"spin\":{},\"held\":{}}}",
+                json_vec2(ball.location()),
+                json_vec2(ball.velocity()),
+                json_number(ball.radius()),
+                json_number(ball.spin()),
+                game.ball_is_held(i)
+            )
+        })
+        .collect();
+    let effects: Vec<String> = game
+        .effects()
+        .iter()
+        .map(|effect| {
+            format!(
+                "{{\"power_up\":\"{:?}\",\"remaining\":{}}}",
+                effect.power_up(),
+                json_number(effect.remaining())
+            )
+        })
+        .collect();
+
+    let blocks = game.blocks().iter().flatten();
+    let blocks_left = blocks.clone().count();
+    let destructible_left = blocks.filter(|block| block.is_destructible()).count();
+    let score = game.score();
+
+    format!(
+        "{{\"level\":{},\"tick\":{},\"time\":{},\"final\":{},\"phase\":\"{:?}\",\"lives\":{},\
+         \"score\":{{\"points\":{},\"combo\":{},\"multiplier\":{},\"elapsed\":{}}},\
+         \"paddle\":{{\"location\":{},\"velocity\":{},\"dimensions\":{}}},\"balls\":[{}],\
+         \"blocks_left\":{},\"destructible_left\":{},\"capsules\":{},\"projectiles\":{},\
+         \"effects\":[{}],\"events\":{}}}",
+        json_string(name),
+        tick,
+        json_number(tick as f32 * game.dt()),
+        last,
+        game.phase(),
+        game.lives(),
+        score.points(),
+        score.combo(),
+        score.multiplier(),
+        json_number(score.elapsed()),
+        json_vec2(paddle.location()),
+        json_vec2(paddle.velocity()),
+        json_vec2(paddle.dimensions()),
+        balls.join(","),
+        blocks_left,
+        destructible_left,
+        game.capsules().len(),
+        game.projectiles().len(),
+        effects.join(","),
+        events.to_json()
+    )
+}
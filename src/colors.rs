@@ -1,4 +0,0 @@
-pub const CLEAR_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
-pub const WHITE: [f32; 3] = [1., 1., 1.];
-pub const RED: [f32; 3] = [1., 0., 0.];
-pub const BLOCK_COLOR: [f32; 3] = [0.37, 0.64, 0.82];
@@ -18,6 +18,10 @@ pub enum Event {
     GoFullscreen,
     ExitFullscreen,
     WindowResized(u32, u32),
+    Restart,
+    TogglePause,
+    Launch,
+    Fire,
 }
 
 #[derive(Copy, Clone)]
@@ -163,6 +167,51 @@ impl<'a> EventsLoop<'a> {
                         }
                     }
                     WindowEvent::Resized(w, h) => callback(Event::WindowResized(w, h)),
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                virtual_keycode: Some(VirtualKeyCode::R),
+                                state: Pressed,
+                                ..
+                            },
+                        ..
+                    } => callback(Event::Restart),
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                virtual_keycode: Some(VirtualKeyCode::P),
+                                state: Pressed,
+                                ..
+                            },
+                        ..
+                    } => callback(Event::TogglePause),
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                virtual_keycode: Some(VirtualKeyCode::Space),
+                                state: Pressed,
+                                ..
+                            },
+                        ..
+                    } => callback(Event::Launch),
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                virtual_keycode: Some(VirtualKeyCode::Up),
+                                state: Pressed,
+                                ..
+                            },
+                        ..
+                    }
+                    | WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                virtual_keycode: Some(VirtualKeyCode::W),
+                                state: Pressed,
+                                ..
+                            },
+                        ..
+                    } => callback(Event::Fire),
                     WindowEvent::KeyboardInput {
                         input:
                             KeyboardInput {
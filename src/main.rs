@@ -9,18 +9,18 @@ extern crate image;
 extern crate itertools;
 extern crate rand;
 
-mod colors;
 mod events;
 mod gfx_props;
 
+use breakout_core::colors::*;
 use breakout_core::prelude::*;
-use colors::*;
 use events::{Button, ButtonState::Pressed, Event};
 use gfx::traits::FactoryExt;
 use gfx::Device;
-use gfx_glyph::{GlyphBrushBuilder, Section};
+use gfx_glyph::{GlyphBrushBuilder, HorizontalAlign, Layout, Scale, Section, VerticalAlign};
 use gfx_props::*;
 use glutin::GlContext;
+use std::process;
 use std::time::{Duration, Instant};
 
 fn get_paddle_vertices_and_indices(game: &Breakout) -> (Vec<PaddleVertex>, Vec<u16>) {
@@ -33,19 +33,19 @@ fn get_paddle_vertices_and_indices(game: &Breakout) -> (Vec<PaddleVertex>, Vec<u
     vs.extend(&[
         PaddleVertex {
             pos: [right * 2., bottom * 2.],
-            color: WHITE,
+            color: PADDLE_COLOR,
         },
         PaddleVertex {
             pos: [left * 2., bottom * 2.],
-            color: WHITE,
+            color: PADDLE_COLOR,
         },
         PaddleVertex {
             pos: [left * 2., top * 2.],
-            color: WHITE,
+            color: PADDLE_COLOR,
         },
         PaddleVertex {
             pos: [right * 2., top * 2.],
-            color: WHITE,
+            color: PADDLE_COLOR,
         },
     ]);
     is.extend(&[0, 1, 2, 2, 3, 0]);
@@ -53,29 +53,32 @@ fn get_paddle_vertices_and_indices(game: &Breakout) -> (Vec<PaddleVertex>, Vec<u
     (vs, is)
 }
 
-fn get_block_vertices_and_indices(block: &Block) -> (Vec<BlockVertex>, Vec<u16>) {
+fn get_rectangle_vertices_and_indices(
+    dimensions: [f32; 2],
+    color: [f32; 3],
+) -> (Vec<BlockVertex>, Vec<u16>) {
     let (mut vs, mut is) = (vec![], vec![]);
 
-    let [length, height] = block.dimensions();
+    let [length, height] = dimensions;
 
     let (left, top, right, bottom) = (0., height, length, 0.);
 
     vs.extend(&[
         BlockVertex {
             pos: [right * 2., bottom * 2.],
-            color: BLOCK_COLOR,
+            color,
         },
         BlockVertex {
             pos: [left * 2., bottom * 2.],
-            color: BLOCK_COLOR,
+            color,
         },
         BlockVertex {
             pos: [left * 2., top * 2.],
-            color: BLOCK_COLOR,
+            color,
         },
         BlockVertex {
             pos: [right * 2., top * 2.],
-            color: BLOCK_COLOR,
+            color,
         },
     ]);
     is.extend(&[0, 1, 2, 2, 3, 0]);
@@ -113,7 +116,8 @@ pub fn get_block_data<R: gfx::Resources, F: gfx::Factory<R>>(
     main_color: &gfx::handle::RenderTargetView<R, ColorFormat>,
     block: &Block,
 ) -> (gfx::Slice<R>, block_pipe::Data<R>) {
-    let (block_vertices, block_indices) = get_block_vertices_and_indices(block);
+    let (block_vertices, block_indices) =
+        get_rectangle_vertices_and_indices(block.dimensions(), block_color(block));
 
     let (vertex_buffer, slice) =
         factory.create_vertex_buffer_with_slice(&block_vertices, &block_indices[..]);
@@ -128,7 +132,145 @@ pub fn get_block_data<R: gfx::Resources, F: gfx::Factory<R>>(
     )
 }
 
+/// Buffers for every block of a game, matching up with `Breakout::blocks`. Blocks that have
+/// been destroyed don't get any.
+pub fn get_blocks_data<R: gfx::Resources, F: gfx::Factory<R>>(
+    factory: &mut F,
+    main_color: &gfx::handle::RenderTargetView<R, ColorFormat>,
+    game: &Breakout,
+) -> Vec<Option<(gfx::Slice<R>, block_pipe::Data<R>)>> {
+    game.blocks()
+        .iter()
+        .map(|block| {
+            block
+                .as_ref()
+                .map(|block| get_block_data(factory, main_color, block))
+        })
+        .collect()
+}
+
+/// Capsules all share the same shape, so each power-up gets one buffer that is moved around to
+/// draw every capsule of that kind.
+pub fn get_capsule_data<R: gfx::Resources, F: gfx::Factory<R>>(
+    factory: &mut F,
+    main_color: &gfx::handle::RenderTargetView<R, ColorFormat>,
+    power_up: PowerUp,
+) -> (PowerUp, gfx::Slice<R>, block_pipe::Data<R>) {
+    let capsule = Capsule::new(power_up, [0., 0.]);
+    let (capsule_vertices, capsule_indices) =
+        get_rectangle_vertices_and_indices(capsule.dimensions(), capsule_color(power_up));
+
+    let (vertex_buffer, slice) =
+        factory.create_vertex_buffer_with_slice(&capsule_vertices, &capsule_indices[..]);
+
+    (
+        power_up,
+        slice,
+        block_pipe::Data {
+            vbuf: vertex_buffer,
+            corner: [0., 0.],
+            out: main_color.clone(),
+        },
+    )
+}
+
+pub fn get_projectile_data<R: gfx::Resources, F: gfx::Factory<R>>(
+    factory: &mut F,
+    main_color: &gfx::handle::RenderTargetView<R, ColorFormat>,
+) -> (gfx::Slice<R>, block_pipe::Data<R>) {
+    let projectile = Projectile::new([0., 0.]);
+    let (projectile_vertices, projectile_indices) =
+        get_rectangle_vertices_and_indices(projectile.dimensions(), PROJECTILE_COLOR);
+
+    let (vertex_buffer, slice) =
+        factory.create_vertex_buffer_with_slice(&projectile_vertices, &projectile_indices[..]);
+
+    (
+        slice,
+        block_pipe::Data {
+            vbuf: vertex_buffer,
+            corner: [0., 0.],
+            out: main_color.clone(),
+        },
+    )
+}
+
+/// Where campaign progress is kept between runs.
+const PROGRESS_PATH: &str = "breakout-progress.txt";
+
+/// Where the last attempt at a level is saved, so that it can be played back.
+const REPLAY_PATH: &str = "breakout-replay.txt";
+
+fn save_replay(replay: &Replay) {
+    if let Err(error) = replay.save(REPLAY_PATH) {
+        eprintln!("{}: {}", REPLAY_PATH, error);
+    }
+}
+
+fn load_level(path: &str, dt: f32) -> Level {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("{}: {}", path, error);
+        process::exit(1);
+    });
+    let level = Level::parse(&text).unwrap_or_else(|error| {
+        eprintln!("{}: {}", path, error);
+        process::exit(1);
+    });
+    if let Err(error) = level.builder().dt(dt).try_build() {
+        eprintln!("{}: {}", path, error);
+        process::exit(1);
+    }
+    level
+}
+
 fn main() {
+    let dt = 1. / 960.;
+
+    // `--ai` lets the computer play, and `--ai=tracker` lets a much worse computer play. Any
+    // other arguments are levels, played in order as a campaign.
+    let (ai, paths): (Vec<String>, Vec<String>) = std::env::args()
+        .skip(1)
+        .partition(|arg| arg.starts_with("--ai"));
+    let mut controller: Option<Box<dyn PaddleController>> = match ai.last().map(|arg| &arg[..]) {
+        None => None,
+        Some("--ai") | Some("--ai=predictive") => Some(Box::new(PredictiveController::new())),
+        Some("--ai=tracker") => Some(Box::new(TrackingController::new())),
+        Some(arg) => {
+            eprintln!(
+                "unknown AI `{}`, expected `--ai=predictive` or `--ai=tracker`",
+                arg
+            );
+            process::exit(1);
+        }
+    };
+    let levels = if paths.is_empty() {
+        let info = LevelInfo {
+            name: "Level 1".to_owned(),
+            ..LevelInfo::default()
+        };
+        vec![Level::from_game(info, &Breakout::level_1(dt))]
+    } else {
+        paths.iter().map(|path| load_level(path, dt)).collect()
+    };
+
+    // Saved progress is only picked back up if it was saved for these levels.
+    let resumed = Progress::load(PROGRESS_PATH).ok().and_then(|progress| {
+        match Campaign::resume(dt, levels.clone(), progress) {
+            Ok(campaign) => Some(campaign),
+            Err(error) => {
+                eprintln!("{}: {}, starting over", PROGRESS_PATH, error);
+                None
+            }
+        }
+    });
+    let mut campaign = match resumed {
+        Some(campaign) => campaign,
+        None => Campaign::new(dt, levels).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        }),
+    };
+
     let builder = glutin::WindowBuilder::new()
         .with_title("Breakout".to_string())
         .with_dimensions(800, 800);
@@ -178,22 +320,21 @@ fn main() {
         )
         .unwrap();
 
-    let mut game = Breakout::level_1(1. / 960.);
-
-    let (vertices, indices) = get_paddle_vertices_and_indices(&game);
-    let (ball_vertices, ball_indices) = get_ball_vertices_and_indices(&game);
+    let (vertices, indices) = get_paddle_vertices_and_indices(campaign.game());
+    let (ball_vertices, ball_indices) = get_ball_vertices_and_indices(campaign.game());
 
-    let (vertex_buffer, slice) = factory.create_vertex_buffer_with_slice(&vertices, &indices[..]);
+    let (vertex_buffer, mut slice) =
+        factory.create_vertex_buffer_with_slice(&vertices, &indices[..]);
 
-    let (ball_vertex_buffer, ball_slice) =
+    let (ball_vertex_buffer, mut ball_slice) =
         factory.create_vertex_buffer_with_slice(&ball_vertices, &ball_indices[..]);
 
     let mut paddle_data = {
         paddle_pipe::Data {
             vbuf: vertex_buffer,
             corner: [
-                game.paddle().left() * 2. - 1.,
-                game.paddle().bottom() * 2. - 1.,
+                campaign.game().paddle().left() * 2. - 1.,
+                campaign.game().paddle().bottom() * 2. - 1.,
             ],
             out: main_color.clone(),
         }
@@ -202,21 +343,22 @@ fn main() {
     let mut ball_data = ball_pipe::Data {
         vbuf: ball_vertex_buffer,
         midpoint: [
-            game.ball().location().x() * 2. - 1.,
-            game.ball().location().y() * 2. - 1.,
+            campaign.game().ball().location().x() * 2. - 1.,
+            campaign.game().ball().location().y() * 2. - 1.,
         ],
-        color: RED,
-        radius: game.ball().radius() * 2.,
+        color: BALL_COLOR,
+        radius: campaign.game().ball().radius() * 2.,
         out: main_color.clone(),
     };
 
-    let mut block_data = vec![];
-    let mut block_slice = vec![];
-    for block in game.blocks() {
-        let (slice, data) = get_block_data(&mut factory, &main_color, block.as_ref().unwrap());
-        block_data.push(data);
-        block_slice.push(slice);
-    }
+    let mut block_data = get_blocks_data(&mut factory, &main_color, campaign.game());
+
+    let mut capsule_data: Vec<_> = PowerUp::ALL
+        .iter()
+        .map(|&power_up| get_capsule_data(&mut factory, &main_color, power_up))
+        .collect();
+
+    let (projectile_slice, mut projectile_data) = get_projectile_data(&mut factory, &main_color);
 
     let nanos_per_update = Duration::from_secs(1) / 960;
 
@@ -229,6 +371,13 @@ fn main() {
     let mut window_size = (800.0, 800.0);
     let mut last_update = Instant::now();
     let mut needs_update = false;
+    let mut needs_resize = false;
+    let mut needs_blocks = false;
+
+    // Input is gathered into a command that is applied on the next tick, and every tick's
+    // command is recorded.
+    let mut command = Command::default();
+    let mut replay = Replay::new(campaign.game());
     while running {
         // fetch events
         events_loop.poll_events(|event| {
@@ -246,17 +395,31 @@ fn main() {
                     // cube.update_ratio(w as f32 / h as f32);
                     window_size = (w as f32, h as f32);
                 }
+                Event::Restart => match campaign.game().phase() {
+                    Phase::GameOver => {
+                        campaign.restart_level();
+                        replay = Replay::new(campaign.game());
+                        needs_resize = true;
+                        needs_blocks = true;
+                    }
+                    // Only the last level stays cleared, so this starts the campaign over.
+                    Phase::LevelCleared => {
+                        campaign = Campaign::new(dt, campaign.levels().to_vec()).unwrap();
+                        replay = Replay::new(campaign.game());
+                        needs_resize = true;
+                        needs_blocks = true;
+                    }
+                    _ => (),
+                },
+                Event::Launch => command.launch = true,
+                Event::Fire => command.fire = true,
+                Event::TogglePause => command.pause = true,
                 Event::Button { button, state }
                     if button == Button::Left || button == Button::Right =>
                 {
-                    let speed = 0.70;
-                    let velocity = speed * if button == Button::Left { -1. } else { 1. };
+                    let direction = if button == Button::Left { -1. } else { 1. };
 
-                    if state == Pressed {
-                        game.paddle_mut().set_velocity([velocity, 0.]);
-                    } else {
-                        game.paddle_mut().set_velocity([0., 0.]);
-                    }
+                    command.steer = if state == Pressed { direction } else { 0. };
                 }
                 _ => (),
             }
@@ -264,7 +427,61 @@ fn main() {
 
         let mut max_fall_behind = Duration::from_secs(1) / 15;
         while last_update.elapsed() >= nanos_per_update {
-            game.tick();
+            // The AI steers and presses buttons, but the keyboard can still pause.
+            if let Some(ref mut controller) = controller {
+                let ai = controller.command(campaign.game());
+                command = Command {
+                    pause: command.pause,
+                    ..ai
+                };
+            }
+
+            campaign.game_mut().apply(command);
+            replay.record(command);
+            // Buttons only press once, but the paddle keeps being steered.
+            command = Command::steer(command.steer);
+
+            let events = campaign.tick();
+            let level_changed = events
+                .iter()
+                .any(|event| matches!(event, GameEvent::LevelCleared { .. }));
+
+            for event in events {
+                match event {
+                    // After a level change, in this tick or an earlier one this frame, the buffers
+                    // are still the old level's. Every block gets new ones after the ticks anyway.
+                    GameEvent::BlockDamaged { index, .. } if !(level_changed || needs_blocks) => {
+                        // The block's color depends on its hit points, so it needs new vertices.
+                        // It may also have been destroyed later in the same tick, by an
+                        // explosion or a second laser bolt, and then it isn't drawn at all.
+                        block_data[index] = campaign.game().blocks()[index]
+                            .as_ref()
+                            .map(|block| get_block_data(&mut factory, &main_color, block));
+                    }
+                    // Power-ups change the size of the paddle and ball.
+                    GameEvent::PowerUpCaught(_)
+                    | GameEvent::PowerUpExpired(_)
+                    | GameEvent::BallLost => needs_resize = true,
+                    GameEvent::GameOver => save_replay(&replay),
+                    GameEvent::LevelCleared { .. } => {
+                        save_replay(&replay);
+                        replay = Replay::new(campaign.game());
+
+                        // A finished campaign starts from the beginning next time. Otherwise
+                        // the next level has taken this one's place and needs its blocks.
+                        if campaign.is_finished() {
+                            let _ = std::fs::remove_file(PROGRESS_PATH);
+                        } else {
+                            if let Err(error) = campaign.progress().save(PROGRESS_PATH) {
+                                eprintln!("{}: {}", PROGRESS_PATH, error);
+                            }
+                            needs_blocks = true;
+                        }
+                        needs_resize = true;
+                    }
+                    _ => (),
+                }
+            }
             last_update += nanos_per_update;
             needs_update = true;
             match max_fall_behind.checked_sub(nanos_per_update) {
@@ -277,6 +494,30 @@ fn main() {
             };
         }
 
+        if needs_resize {
+            let (vertices, indices) = get_paddle_vertices_and_indices(campaign.game());
+            let (vertex_buffer, paddle_slice) =
+                factory.create_vertex_buffer_with_slice(&vertices, &indices[..]);
+            paddle_data.vbuf = vertex_buffer;
+            slice = paddle_slice;
+
+            let (vertices, indices) = get_ball_vertices_and_indices(campaign.game());
+            let (vertex_buffer, new_ball_slice) =
+                factory.create_vertex_buffer_with_slice(&vertices, &indices[..]);
+            ball_data.vbuf = vertex_buffer;
+            ball_data.radius = campaign.game().ball().radius() * 2.;
+            ball_slice = new_ball_slice;
+
+            needs_resize = false;
+        }
+
+        if needs_blocks {
+            block_data = get_blocks_data(&mut factory, &main_color, campaign.game());
+            needs_blocks = false;
+        }
+
+        let game = campaign.game();
+
         if needs_update {
             {
                 paddle_data.corner = [
@@ -284,22 +525,40 @@ fn main() {
                     game.paddle().bottom() * 2. - 1.,
                 ];
             }
-            ball_data.midpoint = [
-                game.ball().location().x() * 2. - 1.,
-                game.ball().location().y() * 2. - 1.,
-            ];
         }
 
         encoder.clear(&ball_data.out, CLEAR_COLOR);
-        encoder.draw(&ball_slice, &ball_pso, &ball_data);
+
+        // Every ball is the same size, so they all share one buffer.
+        for ball in game.balls() {
+            ball_data.midpoint = [
+                ball.location().x() * 2. - 1.,
+                ball.location().y() * 2. - 1.,
+            ];
+            encoder.draw(&ball_slice, &ball_pso, &ball_data);
+        }
         encoder.draw(&slice, &pso, &paddle_data);
 
-        for (_, block_slice, block_data) in
-            izip!(game.blocks(), &block_slice, &block_data).filter(|(block, _, _)| block.is_some())
+        for (block_slice, block_data) in izip!(game.blocks(), &block_data)
+            .filter_map(|(block, buffers)| block.as_ref().and(buffers.as_ref()))
         {
             encoder.draw(block_slice, &block_pso, block_data);
         }
 
+        for projectile in game.projectiles() {
+            projectile_data.corner = [projectile.left() * 2. - 1., projectile.bottom() * 2. - 1.];
+            encoder.draw(&projectile_slice, &block_pso, &projectile_data);
+        }
+
+        for capsule in game.capsules() {
+            let (_, capsule_slice, data) = capsule_data
+                .iter_mut()
+                .find(|(power_up, _, _)| *power_up == capsule.power_up())
+                .unwrap();
+            data.corner = [capsule.left() * 2. - 1., capsule.bottom() * 2. - 1.];
+            encoder.draw(capsule_slice, &block_pso, data);
+        }
+
         frame_count += 1;
 
         if last_fps_update.elapsed() > Duration::from_secs(1) {
@@ -308,7 +567,17 @@ fn main() {
             last_fps_update = Instant::now();
         }
 
-        let text = format!("{}\n{}", fps_text, vsync_text);
+        let score = game.score();
+        let text = format!(
+            "{}\n{}\nLevel {}: {}\nLives: {}\nScore: {} (x{})",
+            fps_text,
+            vsync_text,
+            campaign.level() + 1,
+            campaign.levels()[campaign.level()].info().name,
+            game.lives(),
+            score.points(),
+            score.multiplier()
+        );
 
         let section = Section {
             text: &text,
@@ -318,6 +587,27 @@ fn main() {
 
         glyph_brush.queue(section);
 
+        let banner = match game.phase() {
+            Phase::Serve => Some("Press Space to launch"),
+            Phase::Playing => None,
+            Phase::Paused => Some("PAUSED"),
+            Phase::LevelCleared => Some("ALL LEVELS CLEARED\nPress R to play again"),
+            Phase::GameOver => Some("GAME OVER\nPress R to restart"),
+        };
+
+        if let Some(banner) = banner {
+            glyph_brush.queue(Section {
+                text: banner,
+                screen_position: (window_size.0 * 0.5, window_size.1 * 0.5),
+                scale: Scale::uniform(32.),
+                color: [1., 1., 1., 1.],
+                layout: Layout::default()
+                    .h_align(HorizontalAlign::Center)
+                    .v_align(VerticalAlign::Center),
+                ..Section::default()
+            });
+        }
+
         glyph_brush
             .draw_queued(&mut encoder, &main_color, &depth)
             .unwrap();